@@ -0,0 +1,92 @@
+// Copyright 2026 pyke.io
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Measures how much it costs `Chat` to skip over IRC messages that don't produce an event.
+//!
+//! A local server sends a burst of `PING`s, `CAP ACK`s and numeric replies followed by a single `PRIVMSG`; we then
+//! count how many times the stream is polled, and how long it takes, before the message comes through.
+
+use std::{
+	future::poll_fn,
+	io::{Read, Write},
+	net::TcpListener,
+	thread,
+	time::{Duration, Instant}
+};
+
+use brainrot::twitch::{Anonymous, Capabilities, Chat, ChatEvent};
+use futures_util::StreamExt;
+
+const FILTERED_LINES: &[&str] = &[
+	"PING :tmi.twitch.tv\r\n",
+	":tmi.twitch.tv CAP * ACK :twitch.tv/tags\r\n",
+	":tmi.twitch.tv 372 justinfan24340 :You are in a maze of twisty passages, all alike.\r\n",
+	":tmi.twitch.tv FOO #miyukiwei\r\n"
+];
+
+const MESSAGE: &str = "@badge-info=;badges=;color=;display-name=miyukiwei;emotes=;id=b34ccfc7-4977-403a-8a94-33c6bac34fb8;mod=0;room-id=1;tmi-sent-ts=1;user-id=1;user-type= :miyukiwei!miyukiwei@miyukiwei.tmi.twitch.tv PRIVMSG #miyukiwei :hi\r\n";
+
+fn serve(filtered: usize) -> u16 {
+	let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+	let port = listener.local_addr().unwrap().port();
+	thread::spawn(move || {
+		let (mut socket, _) = listener.accept().unwrap();
+		let mut burst = String::from(":tmi.twitch.tv 001 justinfan24340 :Welcome, GLHF!\r\n");
+		for i in 0..filtered {
+			burst.push_str(FILTERED_LINES[i % FILTERED_LINES.len()]);
+		}
+		burst.push_str(MESSAGE);
+		socket.write_all(burst.as_bytes()).unwrap();
+		// Keep the connection open until the client hangs up.
+		let mut buf = [0; 1024];
+		while socket.read(&mut buf).is_ok_and(|n| n > 0) {}
+	});
+	port
+}
+
+async fn run(filtered: usize) -> (usize, Duration) {
+	let port = serve(filtered);
+	let mut chat = Chat::builder()
+		.server("127.0.0.1")
+		.port(port)
+		.tls(false)
+		.capabilities(Capabilities::none())
+		.connect("miyukiwei", Anonymous)
+		.await
+		.unwrap();
+
+	let mut polls = 0;
+	let start = Instant::now();
+	loop {
+		let event = poll_fn(|cx| {
+			polls += 1;
+			chat.poll_next_unpin(cx)
+		})
+		.await;
+		match event {
+			Some(Ok(ChatEvent::Message { .. })) => break,
+			Some(Ok(_)) => {}
+			event => panic!("stream ended before the message arrived: {event:?}")
+		}
+	}
+	(polls, start.elapsed())
+}
+
+fn main() {
+	let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+	for filtered in [1_000, 10_000, 100_000] {
+		let (polls, elapsed) = runtime.block_on(run(filtered));
+		println!("{filtered:>7} filtered messages: {polls:>7} polls, {:>6.0} ns/message ({elapsed:.2?} total)", elapsed.as_nanos() as f64 / filtered as f64);
+	}
+}
@@ -0,0 +1,44 @@
+// Copyright 2026 pyke.io
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{error::Error as StdError, future::Future, time::Duration};
+
+use bytes::{Bytes, BytesMut};
+
+pub trait Response: Send + Sized {
+	type Error: StdError + Send;
+
+	fn status_code(&self) -> u16;
+
+	fn recv_chunk(&mut self) -> impl Future<Output = Result<Option<Bytes>, Self::Error>> + Send + Sync + '_;
+
+	fn recv_all(mut self) -> impl Future<Output = Result<BytesMut, Self::Error>> + Send {
+		async move {
+			let mut out = BytesMut::new();
+			while let Some(frame) = self.recv_chunk().await? {
+				out.extend_from_slice(&frame);
+			}
+			Ok(out)
+		}
+	}
+}
+
+pub trait RequestExecutor: Send + Sync + 'static {
+	type Response: Response + 'static;
+	type Error: StdError + Send;
+
+	fn make_request(&self, req: http::Request<Bytes>) -> impl Future<Output = Result<Self::Response, Self::Error>> + Send + Sync + '_;
+
+	fn sleep(dur: Duration) -> impl Future<Output = ()> + Send + Sync;
+}
@@ -19,4 +19,6 @@ pub mod twitch;
 #[cfg(feature = "youtube")]
 pub mod youtube;
 
+#[cfg(any(feature = "twitch-helix", feature = "twitch-emotes", feature = "youtube"))]
+pub(crate) mod executor;
 pub(crate) mod util;
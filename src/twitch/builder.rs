@@ -0,0 +1,213 @@
+// Copyright 2026 pyke.io
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{sync::Arc, time::Duration};
+
+use irc::{client::prelude::Config, proto::Capability};
+
+use super::{
+	Chat, Error, IntoTwitchIdentity,
+	transport::{self, ConnectOptions, Transport}
+};
+
+const TWITCH_CAPABILITY_TAGS: Capability = Capability::Custom("twitch.tv/tags");
+const TWITCH_CAPABILITY_MEMBERSHIP: Capability = Capability::Custom("twitch.tv/membership");
+const TWITCH_CAPABILITY_COMMANDS: Capability = Capability::Custom("twitch.tv/commands");
+
+/// The [IRC capabilities](https://dev.twitch.tv/docs/chat/irc/#twitch-specific-irc-capabilities) to request from
+/// Twitch. All are requested by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Capabilities {
+	/// `twitch.tv/tags`, which adds metadata like user IDs, badges & emotes to messages. Almost every [`ChatEvent`]
+	/// is built from tags, so without this capability, very few events will be received.
+	///
+	/// [`ChatEvent`]: super::ChatEvent
+	pub tags: bool,
+	/// `twitch.tv/commands`, which enables Twitch-specific commands like `USERNOTICE` (subs, raids, etc.), `CLEARCHAT`
+	/// and `ROOMSTATE`.
+	pub commands: bool,
+	/// `twitch.tv/membership`, which enables `JOIN` & `PART` messages for other users. This generates a lot of traffic
+	/// in large channels.
+	pub membership: bool
+}
+
+impl Capabilities {
+	/// Requests all capabilities.
+	pub const fn all() -> Self {
+		Self {
+			tags: true,
+			commands: true,
+			membership: true
+		}
+	}
+
+	/// Requests no capabilities, i.e. plain IRC.
+	pub const fn none() -> Self {
+		Self {
+			tags: false,
+			commands: false,
+			membership: false
+		}
+	}
+
+	pub(crate) fn to_request(self) -> Vec<Capability> {
+		[
+			(self.commands, TWITCH_CAPABILITY_COMMANDS),
+			(self.membership, TWITCH_CAPABILITY_MEMBERSHIP),
+			(self.tags, TWITCH_CAPABILITY_TAGS)
+		]
+		.into_iter()
+		.filter_map(|(enabled, capability)| enabled.then_some(capability))
+		.collect()
+	}
+}
+
+impl Default for Capabilities {
+	fn default() -> Self {
+		Self::all()
+	}
+}
+
+/// Configures the connection made by [`Chat`].
+///
+/// [`Chat::new`] connects to Twitch with the default configuration; use a builder to connect to a different server
+/// (e.g. a local IRC server in tests), connect [over WebSocket](Transport::WebSocket), request fewer capabilities, or
+/// tune the keepalive.
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use brainrot::twitch::{Anonymous, Capabilities, Chat};
+///
+/// # #[tokio::main]
+/// # async fn main() -> anyhow::Result<()> {
+/// let mut capabilities = Capabilities::all();
+/// capabilities.membership = false;
+/// let builder = Chat::builder().capabilities(capabilities).ping_timeout(Duration::from_secs(10));
+/// let mut client = builder.connect("miyukiwei", Anonymous).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChatBuilder {
+	transport: Transport,
+	server: Option<String>,
+	port: Option<u16>,
+	use_tls: bool,
+	capabilities: Capabilities,
+	ping_interval: Option<Duration>,
+	ping_timeout: Option<Duration>,
+	anonymous_nickname: Option<String>
+}
+
+impl ChatBuilder {
+	pub fn new() -> Self {
+		Self {
+			transport: Transport::Tcp,
+			server: None,
+			port: None,
+			use_tls: true,
+			capabilities: Capabilities::all(),
+			ping_interval: None,
+			ping_timeout: None,
+			anonymous_nickname: None
+		}
+	}
+
+	/// How to connect to the server. Defaults to [`Transport::Tcp`].
+	pub fn transport(mut self, transport: Transport) -> Self {
+		self.transport = transport;
+		self
+	}
+
+	/// The hostname of the server to connect to. Defaults to `irc.chat.twitch.tv`, or `irc-ws.chat.twitch.tv` when
+	/// using [`Transport::WebSocket`].
+	pub fn server(mut self, server: impl Into<String>) -> Self {
+		self.server = Some(server.into());
+		self
+	}
+
+	/// The port to connect to. Defaults to `6697` when using TLS, or `6667` otherwise; or when using
+	/// [`Transport::WebSocket`], `443` and `80` respectively.
+	pub fn port(mut self, port: u16) -> Self {
+		self.port = Some(port);
+		self
+	}
+
+	/// Whether to connect over TLS. Enabled by default; disabling it sends your OAuth token in plaintext, so this
+	/// should only be disabled to connect to a local server.
+	pub fn tls(mut self, use_tls: bool) -> Self {
+		self.use_tls = use_tls;
+		self
+	}
+
+	/// The capabilities to request after connecting. See [`Capabilities`].
+	pub fn capabilities(mut self, capabilities: Capabilities) -> Self {
+		self.capabilities = capabilities;
+		self
+	}
+
+	/// How long the connection may be idle before we send a `PING` to check that it's still alive. Defaults to 3
+	/// minutes. Only whole seconds are used.
+	pub fn ping_interval(mut self, interval: Duration) -> Self {
+		self.ping_interval = Some(interval);
+		self
+	}
+
+	/// How long to wait for a response to our `PING` before considering the connection dead. Defaults to 20 seconds.
+	/// Only whole seconds are used.
+	pub fn ping_timeout(mut self, timeout: Duration) -> Self {
+		self.ping_timeout = Some(timeout);
+		self
+	}
+
+	/// The nickname to use when connecting with an [`Anonymous`](super::Anonymous) identity. Twitch only accepts
+	/// anonymous nicknames of the form `justinfan` followed by digits, e.g. `justinfan12345`.
+	pub fn anonymous_nickname(mut self, nickname: impl Into<String>) -> Self {
+		self.anonymous_nickname = Some(nickname.into());
+		self
+	}
+
+	/// Connects to a channel with the configured options. See [`Chat::new`].
+	pub async fn connect(self, channel: impl AsRef<str>, auth: impl IntoTwitchIdentity) -> Result<Chat, Error> {
+		let config = Config {
+			server: Some(self.server.unwrap_or_else(|| self.transport.default_server().to_owned())),
+			port: self.port,
+			use_tls: Some(self.use_tls),
+			ping_time: self.ping_interval.map(duration_secs),
+			ping_timeout: self.ping_timeout.map(duration_secs),
+			..Default::default()
+		};
+		let options = ConnectOptions {
+			config,
+			capabilities: self.capabilities,
+			transport: self.transport,
+			identity: Arc::new(auth.into_identity()),
+			anonymous_nickname: self.anonymous_nickname
+		};
+		let connection = transport::connect(options.clone()).await?;
+		Ok(Chat::from_connection(connection, options, channel.as_ref()))
+	}
+}
+
+impl Default for ChatBuilder {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+fn duration_secs(duration: Duration) -> u32 {
+	duration.as_secs().clamp(1, u32::MAX as u64) as u32
+}
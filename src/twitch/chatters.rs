@@ -0,0 +1,156 @@
+// Copyright 2026 pyke.io
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{HashMap, HashSet};
+
+use super::ChatEvent;
+
+/// Keeps track of the users in each joined channel.
+///
+/// Feed every event received from [`Chat`](super::Chat) into [`Chatters::handle_event`]. Each channel's list is seeded
+/// from the [`ChatEvent::MemberChunk`]s Twitch sends upon joining it, then kept up to date with [`ChatEvent::Join`] &
+/// [`ChatEvent::Part`]. This requires the [`membership`](super::Capabilities::membership) capability.
+///
+/// Twitch only reports membership in channels with fewer than 1,000 chatters, and batches changes every few seconds,
+/// so the list is best treated as an approximation. Users that haven't sent a message are included.
+///
+/// Twitch also sends a [`ChatEvent::Part`] for the connected account when [parting](super::Chat::part) a channel. If
+/// the account's username is set via [`Chatters::with_account`], the channel's list is dropped when that happens;
+/// otherwise, call [`Chatters::forget_channel`] after parting a channel so its list doesn't go stale.
+///
+/// ```
+/// use brainrot::twitch::{Chatters, parse_line};
+///
+/// let mut chatters = Chatters::new().with_account("yukifan4");
+/// chatters.handle_event(&parse_line(":yukifan4.tmi.twitch.tv 353 yukifan4 = #miyukiwei :yukifan4 miyu")?.unwrap());
+/// chatters.handle_event(&parse_line(":yukifan4.tmi.twitch.tv 366 yukifan4 #miyukiwei :End of /NAMES list")?.unwrap());
+/// chatters.handle_event(&parse_line(":sinder!sinder@sinder.tmi.twitch.tv JOIN #miyukiwei")?.unwrap());
+/// assert_eq!(chatters.count("miyukiwei"), 3);
+/// # Ok::<_, brainrot::twitch::ParseError>(())
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct Chatters {
+	channels: HashMap<String, HashSet<String>>,
+	/// Member lists still being received, which replace the channel's list once complete.
+	loading: HashMap<String, HashSet<String>>,
+	/// The connected account's username.
+	account: Option<String>
+}
+
+impl Chatters {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Sets the username of the connected account, so channels it parts are forgotten.
+	pub fn with_account(mut self, username: impl Into<String>) -> Self {
+		self.account = Some(username.into().to_lowercase());
+		self
+	}
+
+	/// Updates the lists with an event.
+	pub fn handle_event(&mut self, event: &ChatEvent) {
+		match event {
+			ChatEvent::MemberChunk { channel, names } => {
+				self.loading.entry(channel.name.clone()).or_default().extend(names.iter().cloned());
+			}
+			ChatEvent::EndOfMembers { channel } => {
+				let names = self.loading.remove(&channel.name).unwrap_or_default();
+				self.channels.insert(channel.name.clone(), names);
+			}
+			ChatEvent::Join { channel, username } => {
+				if let Some(names) = self.loading.get_mut(&channel.name) {
+					names.insert(username.clone());
+				}
+				self.channels.entry(channel.name.clone()).or_default().insert(username.clone());
+			}
+			ChatEvent::Part { channel, username } if self.account.as_ref() == Some(username) => {
+				self.forget_channel(&channel.name);
+			}
+			ChatEvent::Part { channel, username } => {
+				if let Some(names) = self.loading.get_mut(&channel.name) {
+					names.remove(username);
+				}
+				if let Some(names) = self.channels.get_mut(&channel.name) {
+					names.remove(username);
+				}
+			}
+			_ => {}
+		}
+	}
+
+	/// Returns the usernames of the users in a channel, or `None` if no members are known for the channel.
+	pub fn get(&self, channel: impl AsRef<str>) -> Option<impl Iterator<Item = &str> + '_> {
+		self.channels
+			.get(channel.as_ref().trim_start_matches('#'))
+			.map(|names| names.iter().map(String::as_str))
+	}
+
+	/// Whether a user is in a channel.
+	pub fn contains(&self, channel: impl AsRef<str>, username: impl AsRef<str>) -> bool {
+		self.channels
+			.get(channel.as_ref().trim_start_matches('#'))
+			.is_some_and(|names| names.contains(username.as_ref()))
+	}
+
+	/// The number of users in a channel.
+	pub fn count(&self, channel: impl AsRef<str>) -> usize {
+		self.channels.get(channel.as_ref().trim_start_matches('#')).map_or(0, HashSet::len)
+	}
+
+	/// Forgets a channel's users, e.g. after [parting](super::Chat::part) it.
+	pub fn forget_channel(&mut self, channel: impl AsRef<str>) {
+		let channel = channel.as_ref().trim_start_matches('#');
+		self.channels.remove(channel);
+		self.loading.remove(channel);
+	}
+
+	pub fn clear(&mut self) {
+		self.channels.clear();
+		self.loading.clear();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Chatters;
+	use crate::twitch::parse_line;
+
+	fn feed(chatters: &mut Chatters, lines: &[&str]) {
+		for line in lines {
+			chatters.handle_event(&parse_line(line).unwrap().unwrap());
+		}
+	}
+
+	#[test]
+	fn tracks_membership() {
+		let mut chatters = Chatters::new().with_account("YukiFan4");
+		feed(
+			&mut chatters,
+			&[
+				":yukifan4!yukifan4@yukifan4.tmi.twitch.tv JOIN #miyukiwei",
+				":yukifan4.tmi.twitch.tv 353 yukifan4 = #miyukiwei :yukifan4 miyu",
+				":yukifan4.tmi.twitch.tv 366 yukifan4 #miyukiwei :End of /NAMES list",
+				":sinder!sinder@sinder.tmi.twitch.tv JOIN #miyukiwei",
+				":miyu!miyu@miyu.tmi.twitch.tv PART #miyukiwei"
+			]
+		);
+		assert_eq!(chatters.count("#miyukiwei"), 2);
+		assert!(chatters.contains("miyukiwei", "sinder"));
+		assert!(!chatters.contains("miyukiwei", "miyu"));
+
+		feed(&mut chatters, &[":yukifan4!yukifan4@yukifan4.tmi.twitch.tv PART #miyukiwei"]);
+		assert!(chatters.get("miyukiwei").is_none());
+	}
+}
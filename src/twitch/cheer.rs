@@ -0,0 +1,115 @@
+// Copyright 2026 pyke.io
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{collections::HashSet, num::NonZeroU32};
+
+use super::{MessageSegment, event::split_words};
+
+/// Prefixes of the global cheermotes available in every channel.
+const GLOBAL_CHEERMOTE_PREFIXES: &[&str] = &[
+	"Cheer",
+	"DoodleCheer",
+	"BibleThump",
+	"cheerwhal",
+	"Corgo",
+	"Scoops",
+	"uni",
+	"ShowLove",
+	"Party",
+	"SeemsGood",
+	"Pride",
+	"Kappa",
+	"FrankerZ",
+	"HeyGuys",
+	"DansGame",
+	"EleGiggle",
+	"TriHard",
+	"Kreygasm",
+	"4Head",
+	"SwiftRage",
+	"NotLikeThis",
+	"FailFish",
+	"VoHiYo",
+	"PJSalt",
+	"MrDestructoid",
+	"bday",
+	"RIPCheer",
+	"Shamrock",
+	"BitBoss",
+	"Streamlabs",
+	"Muxy",
+	"HolidayCheer",
+	"Goal",
+	"Anon",
+	"Charity"
+];
+
+/// The set of cheermote prefixes used to recognize cheers (e.g. `Cheer100`) in messages containing Bits.
+///
+/// By default, only [global cheermotes](Cheermotes::global) are recognized. Channels can have their own custom
+/// cheermotes; the full list for a channel can be fetched with the
+/// [Get Cheermotes](https://dev.twitch.tv/docs/api/reference/#get-cheermotes) API and supplied to
+/// [`Chat::with_cheermotes`](super::Chat::with_cheermotes).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cheermotes {
+	prefixes: HashSet<String>
+}
+
+impl Cheermotes {
+	/// Creates a set recognizing only the given prefixes.
+	pub fn new<S: AsRef<str>>(prefixes: impl IntoIterator<Item = S>) -> Self {
+		Self {
+			prefixes: prefixes.into_iter().map(|prefix| prefix.as_ref().to_ascii_lowercase()).collect()
+		}
+	}
+
+	/// The prefixes of Twitch's global cheermotes, like `Cheer` & `Kappa`.
+	pub fn global() -> Self {
+		Self::new(GLOBAL_CHEERMOTE_PREFIXES)
+	}
+
+	/// Adds additional prefixes to the set, e.g. a channel's custom cheermotes.
+	pub fn extend<S: AsRef<str>>(&mut self, prefixes: impl IntoIterator<Item = S>) {
+		self.prefixes
+			.extend(prefixes.into_iter().map(|prefix| prefix.as_ref().to_ascii_lowercase()));
+	}
+
+	pub fn contains(&self, prefix: &str) -> bool {
+		self.prefixes.contains(&prefix.to_ascii_lowercase())
+	}
+
+	/// Parses a single word like `Cheer100` into its prefix and amount.
+	fn parse_word<'w>(&self, word: &'w str) -> Option<(&'w str, NonZeroU32)> {
+		let split = word.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+		let (prefix, amount) = word.split_at(split);
+		if prefix.is_empty() || amount.is_empty() || !self.contains(prefix) {
+			return None;
+		}
+		Some((prefix, amount.parse().ok()?))
+	}
+
+	/// Splits cheers out of the text segments of a message.
+	pub(crate) fn split_segments(&self, segments: Vec<MessageSegment>) -> Vec<MessageSegment> {
+		split_words(segments, |word| {
+			let (prefix, amount) = self.parse_word(word)?;
+			Some(MessageSegment::Cheer { prefix: prefix.to_owned(), amount })
+		})
+	}
+}
+
+impl Default for Cheermotes {
+	fn default() -> Self {
+		Self::global()
+	}
+}
@@ -0,0 +1,692 @@
+// Copyright 2026 pyke.io
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Resolve emotes from third-party providers: [BetterTTV](https://betterttv.com/),
+//! [FrankerFaceZ](https://www.frankerfacez.com/) and [7TV](https://7tv.app/).
+//!
+//! Twitch doesn't know about these emotes, so they arrive as plain text. [`ThirdPartyEmotes`] loads the global and
+//! per-channel emote sets of each provider through a [`RequestExecutor`], and splits the words of
+//! [`MessageSegment::Text`] segments that match a loaded emote into [`MessageSegment::ThirdPartyEmote`] segments.
+//!
+//! ```no_run
+//! # use brainrot::twitch::{emotes::{EmoteError, ThirdPartyEmotes}, ChatEvent, RequestExecutor};
+//! # async fn run<E: RequestExecutor>(executor: E, mut event: ChatEvent) -> Result<(), EmoteError<E>> {
+//! let mut emotes = ThirdPartyEmotes::new(executor);
+//! emotes.load_global().await?;
+//! // The room ID of the channel, as seen in `Channel::room_id`.
+//! emotes.load_channel(41245072).await?;
+//! // ...
+//! emotes.apply(&mut event);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{collections::HashMap, error::Error as StdError, fmt};
+
+use bytes::Bytes;
+use http::Request;
+use serde::{Deserialize, de::DeserializeOwned};
+
+use super::{ChatEvent, MessageSegment, event::split_words};
+use crate::executor::{RequestExecutor, Response};
+
+/// Global BetterTTV emotes that are drawn over the previous emote rather than next to it. BetterTTV's API doesn't mark
+/// these, so they have to be listed by hand.
+const BTTV_ZERO_WIDTH_EMOTES: &[&str] = &["SoSnowy", "IceCold", "SantaHat", "TopHat", "ReinDeer", "CandyCane", "cvMask", "cvHazmat"];
+
+/// 7TV's flag for zero-width emotes, on either the emote itself or its entry in an emote set.
+const SEVENTV_EMOTE_ZERO_WIDTH: u32 = 1 << 8;
+const SEVENTV_ACTIVE_EMOTE_ZERO_WIDTH: u32 = 1 << 0;
+
+/// A third-party emote provider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EmoteProvider {
+	BetterTtv,
+	FrankerFaceZ,
+	SevenTv
+}
+
+impl EmoteProvider {
+	/// All providers, in order of precedence: if multiple providers have an emote with the same name, the first
+	/// provider's emote is used.
+	pub const ALL: [EmoteProvider; 3] = [EmoteProvider::SevenTv, EmoteProvider::BetterTtv, EmoteProvider::FrankerFaceZ];
+}
+
+impl fmt::Display for EmoteProvider {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(match self {
+			Self::BetterTtv => "BetterTTV",
+			Self::FrankerFaceZ => "FrankerFaceZ",
+			Self::SevenTv => "7TV"
+		})
+	}
+}
+
+/// An image of an emote at a particular size.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EmoteUrl {
+	/// The image's size relative to the smallest size, e.g. `1` for inline display, or `2`, `3` or `4` for
+	/// high-density displays. Which scales are available depends on the provider.
+	pub scale: u8,
+	pub url: String
+}
+
+/// An emote loaded from a third-party provider.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Emote {
+	pub provider: EmoteProvider,
+	/// The emote's ID with its provider.
+	pub id: String,
+	/// The word that is replaced by the emote.
+	pub name: String,
+	/// The emote's images, from smallest to largest.
+	pub urls: Vec<EmoteUrl>,
+	/// Whether the emote is meant to be drawn over the previous emote rather than next to it.
+	pub zero_width: bool
+}
+
+impl Emote {
+	fn to_segment(&self) -> MessageSegment {
+		MessageSegment::ThirdPartyEmote {
+			provider: self.provider,
+			id: self.id.clone(),
+			name: self.name.clone(),
+			urls: self.urls.clone(),
+			zero_width: self.zero_width
+		}
+	}
+}
+
+/// Loads & caches third-party emotes, and resolves them in messages.
+///
+/// Emotes are loaded for each enabled provider; by default, all [providers](EmoteProvider) are enabled. Load global
+/// emotes once with [`ThirdPartyEmotes::load_global`], and each channel's emotes with
+/// [`ThirdPartyEmotes::load_channel`] after joining it. Emotes can also be [inserted](ThirdPartyEmotes::insert)
+/// directly, e.g. to restore them from elsewhere or in tests.
+#[derive(Debug, Clone)]
+pub struct ThirdPartyEmotes<E> {
+	executor: E,
+	providers: Vec<EmoteProvider>,
+	/// Emotes by provider & scope, where a scope of `None` holds global emotes.
+	sets: HashMap<(EmoteProvider, Option<u64>), HashMap<String, Emote>>
+}
+
+impl<E: RequestExecutor> ThirdPartyEmotes<E> {
+	pub fn new(executor: E) -> Self {
+		Self {
+			executor,
+			providers: EmoteProvider::ALL.to_vec(),
+			sets: HashMap::new()
+		}
+	}
+
+	/// Only loads and resolves emotes from the given providers, in order of precedence: if multiple providers have an
+	/// emote with the same name, the first provider's emote is used.
+	pub fn with_providers(mut self, providers: impl IntoIterator<Item = EmoteProvider>) -> Self {
+		self.providers = providers.into_iter().collect();
+		self
+	}
+
+	/// Loads (or reloads) the global emotes of each enabled provider.
+	///
+	/// If a provider fails to load, the others are still loaded, and the first error is returned.
+	pub async fn load_global(&mut self) -> Result<(), EmoteError<E>> {
+		self.load(None).await
+	}
+
+	/// Loads (or reloads) the emotes of the channel with the given room ID from each enabled provider. Channels that
+	/// haven't set up a provider simply have no emotes from it.
+	///
+	/// If a provider fails to load, the others are still loaded, and the first error is returned.
+	pub async fn load_channel(&mut self, room_id: u64) -> Result<(), EmoteError<E>> {
+		self.load(Some(room_id)).await
+	}
+
+	async fn load(&mut self, room_id: Option<u64>) -> Result<(), EmoteError<E>> {
+		let mut result = Ok(());
+		for provider in self.providers.clone() {
+			match self.fetch(provider, room_id).await {
+				Ok(emotes) => self.insert(provider, room_id, emotes),
+				Err(e) => {
+					if result.is_ok() {
+						result = Err(e);
+					}
+				}
+			}
+		}
+		result
+	}
+
+	/// Fetches the global emotes (if `room_id` is `None`) or a channel's emotes from a provider, without caching them.
+	pub async fn fetch(&self, provider: EmoteProvider, room_id: Option<u64>) -> Result<Vec<Emote>, EmoteError<E>> {
+		match provider {
+			EmoteProvider::BetterTtv => self.fetch_bttv(room_id).await,
+			EmoteProvider::FrankerFaceZ => self.fetch_ffz(room_id).await,
+			EmoteProvider::SevenTv => self.fetch_seventv(room_id).await
+		}
+	}
+
+	/// Makes a `GET` request, returning `None` if the resource doesn't exist.
+	async fn get_json<R: DeserializeOwned>(&self, provider: EmoteProvider, url: String) -> Result<Option<R>, EmoteError<E>> {
+		let request = Request::get(url).body(Bytes::new())?;
+		let response = self.executor.make_request(request).await.map_err(EmoteError::Executor)?;
+		match response.status_code() {
+			200..=299 => {}
+			404 => return Ok(None),
+			status_code => return Err(EmoteError::Api { provider, status_code })
+		}
+		let mut body = response.recv_all().await.map_err(EmoteError::Receive)?;
+		Ok(Some(simd_json::serde::from_slice(&mut body)?))
+	}
+
+	async fn fetch_bttv(&self, room_id: Option<u64>) -> Result<Vec<Emote>, EmoteError<E>> {
+		let emotes: Vec<BttvEmote> = match room_id {
+			Some(room_id) => {
+				let user: Option<BttvUser> = self
+					.get_json(EmoteProvider::BetterTtv, format!("https://api.betterttv.net/3/cached/users/twitch/{room_id}"))
+					.await?;
+				user.map(|user| user.channel_emotes.into_iter().chain(user.shared_emotes).collect())
+					.unwrap_or_default()
+			}
+			None => self
+				.get_json(EmoteProvider::BetterTtv, "https://api.betterttv.net/3/cached/emotes/global".to_owned())
+				.await?
+				.unwrap_or_default()
+		};
+		Ok(emotes
+			.into_iter()
+			.map(|emote| Emote {
+				provider: EmoteProvider::BetterTtv,
+				urls: (1..=3)
+					.map(|scale| EmoteUrl {
+						scale,
+						url: format!("https://cdn.betterttv.net/emote/{}/{scale}x", emote.id)
+					})
+					.collect(),
+				zero_width: room_id.is_none() && BTTV_ZERO_WIDTH_EMOTES.contains(&emote.code.as_str()),
+				id: emote.id,
+				name: emote.code
+			})
+			.collect())
+	}
+
+	async fn fetch_ffz(&self, room_id: Option<u64>) -> Result<Vec<Emote>, EmoteError<E>> {
+		let url = match room_id {
+			Some(room_id) => format!("https://api.frankerfacez.com/v1/room/id/{room_id}"),
+			None => "https://api.frankerfacez.com/v1/set/global".to_owned()
+		};
+		let Some(response) = self.get_json::<FfzSets>(EmoteProvider::FrankerFaceZ, url).await? else {
+			return Ok(Vec::new());
+		};
+		// The global response also includes sets only available to specific users.
+		let default_sets = response.default_sets;
+		Ok(response
+			.sets
+			.into_iter()
+			.filter(|(id, _)| default_sets.is_empty() || default_sets.iter().any(|default| default.to_string() == *id))
+			.flat_map(|(_, set)| set.emoticons)
+			.map(|emote| {
+				let mut urls: Vec<EmoteUrl> = emote
+					.urls
+					.into_iter()
+					.filter_map(|(scale, url)| {
+						Some(EmoteUrl {
+							scale: scale.parse().ok()?,
+							url: absolute_url(url)
+						})
+					})
+					.collect();
+				urls.sort_by_key(|url| url.scale);
+				Emote {
+					provider: EmoteProvider::FrankerFaceZ,
+					id: emote.id.to_string(),
+					name: emote.name,
+					urls,
+					zero_width: emote.modifier
+				}
+			})
+			.collect())
+	}
+
+	async fn fetch_seventv(&self, room_id: Option<u64>) -> Result<Vec<Emote>, EmoteError<E>> {
+		let set = match room_id {
+			Some(room_id) => self
+				.get_json::<SevenTvUser>(EmoteProvider::SevenTv, format!("https://7tv.io/v3/users/twitch/{room_id}"))
+				.await?
+				.and_then(|user| user.emote_set),
+			None => {
+				self.get_json::<SevenTvSet>(EmoteProvider::SevenTv, "https://7tv.io/v3/emote-sets/global".to_owned())
+					.await?
+			}
+		};
+		Ok(set
+			.and_then(|set| set.emotes)
+			.unwrap_or_default()
+			.into_iter()
+			.filter_map(|emote| {
+				// Emotes that have since been deleted have no data.
+				let data = emote.data?;
+				let mut urls: Vec<EmoteUrl> = data
+					.host
+					.files
+					.iter()
+					.filter(|file| file.format == "WEBP")
+					.filter_map(|file| {
+						Some(EmoteUrl {
+							scale: file.name.split('x').next()?.parse().ok()?,
+							url: absolute_url(format!("{}/{}", data.host.url, file.name))
+						})
+					})
+					.collect();
+				urls.sort_by_key(|url| url.scale);
+				Some(Emote {
+					provider: EmoteProvider::SevenTv,
+					id: emote.id,
+					name: emote.name,
+					urls,
+					zero_width: emote.flags & SEVENTV_ACTIVE_EMOTE_ZERO_WIDTH != 0 || data.flags & SEVENTV_EMOTE_ZERO_WIDTH != 0
+				})
+			})
+			.collect())
+	}
+}
+
+impl<E> ThirdPartyEmotes<E> {
+	/// Caches a provider's emotes, as global emotes if `room_id` is `None` or otherwise as the emotes of the channel
+	/// with that room ID. Any emotes previously cached from the same provider for the same scope are replaced.
+	pub fn insert(&mut self, provider: EmoteProvider, room_id: Option<u64>, emotes: impl IntoIterator<Item = Emote>) {
+		let emotes = emotes.into_iter().map(|emote| (emote.name.clone(), emote)).collect();
+		self.sets.insert((provider, room_id), emotes);
+	}
+
+	/// Forgets a channel's emotes, e.g. after [parting](super::Chat::part) it.
+	pub fn forget_channel(&mut self, room_id: u64) {
+		self.sets.retain(|(_, scope), _| *scope != Some(room_id));
+	}
+
+	/// Looks up an emote by name, preferring the channel's emotes over global emotes.
+	pub fn get(&self, room_id: Option<u64>, name: &str) -> Option<&Emote> {
+		room_id
+			.and_then(|room_id| self.get_in_scope(Some(room_id), name))
+			.or_else(|| self.get_in_scope(None, name))
+	}
+
+	fn get_in_scope(&self, scope: Option<u64>, name: &str) -> Option<&Emote> {
+		self.providers.iter().find_map(|provider| self.sets.get(&(*provider, scope))?.get(name))
+	}
+
+	/// The number of emotes available in a channel, including global emotes.
+	pub fn count(&self, room_id: Option<u64>) -> usize {
+		self.sets
+			.iter()
+			.filter(|((_, scope), _)| scope.is_none() || *scope == room_id)
+			.map(|(_, set)| set.len())
+			.sum()
+	}
+
+	/// Splits third-party emotes out of the text segments of a message sent in the channel with the given room ID.
+	pub fn split_segments(&self, room_id: Option<u64>, segments: Vec<MessageSegment>) -> Vec<MessageSegment> {
+		split_words(segments, |word| self.get(room_id, word).map(Emote::to_segment))
+	}
+
+	/// Resolves third-party emotes in an event's message, if it has one.
+	///
+	/// Emotes are resolved using the emotes of the event's channel, or for events shared from another channel in a
+	/// [shared chat](super::SharedChatSource) session, that channel's emotes. The channel's [room ID](super::Channel)
+	/// must be known; otherwise, only global emotes are resolved.
+	pub fn apply(&self, event: &mut ChatEvent) {
+		let room_id = event
+			.shared_chat()
+			.map(|source| source.room_id)
+			.or_else(|| event.channel().and_then(|channel| channel.room_id));
+		if let Some(segments) = event.segments_mut() {
+			*segments = self.split_segments(room_id, std::mem::take(segments));
+		}
+	}
+}
+
+/// Makes protocol-relative URLs (`//cdn.7tv.app/...`) absolute.
+fn absolute_url(url: String) -> String {
+	if url.starts_with("//") { format!("https:{url}") } else { url }
+}
+
+#[derive(Deserialize)]
+struct BttvEmote {
+	id: String,
+	code: String
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BttvUser {
+	#[serde(default)]
+	channel_emotes: Vec<BttvEmote>,
+	#[serde(default)]
+	shared_emotes: Vec<BttvEmote>
+}
+
+#[derive(Deserialize)]
+struct FfzSets {
+	#[serde(default)]
+	default_sets: Vec<u64>,
+	sets: HashMap<String, FfzSet>
+}
+
+#[derive(Deserialize)]
+struct FfzSet {
+	emoticons: Vec<FfzEmote>
+}
+
+#[derive(Deserialize)]
+struct FfzEmote {
+	id: u64,
+	name: String,
+	urls: HashMap<String, String>,
+	#[serde(default)]
+	modifier: bool
+}
+
+#[derive(Deserialize)]
+struct SevenTvUser {
+	emote_set: Option<SevenTvSet>
+}
+
+#[derive(Deserialize)]
+struct SevenTvSet {
+	emotes: Option<Vec<SevenTvEmote>>
+}
+
+#[derive(Deserialize)]
+struct SevenTvEmote {
+	id: String,
+	name: String,
+	#[serde(default)]
+	flags: u32,
+	data: Option<SevenTvEmoteData>
+}
+
+#[derive(Deserialize)]
+struct SevenTvEmoteData {
+	#[serde(default)]
+	flags: u32,
+	host: SevenTvHost
+}
+
+#[derive(Deserialize)]
+struct SevenTvHost {
+	url: String,
+	files: Vec<SevenTvFile>
+}
+
+#[derive(Deserialize)]
+struct SevenTvFile {
+	name: String,
+	format: String
+}
+
+#[derive(Debug)]
+pub enum EmoteError<E: RequestExecutor> {
+	BadRequest(http::Error),
+	Deserialize(simd_json::Error),
+	Executor(E::Error),
+	Receive(<E::Response as Response>::Error),
+	/// The provider responded with a non-2xx status code (other than `404 Not Found`, which is treated as having no
+	/// emotes).
+	Api {
+		provider: EmoteProvider,
+		status_code: u16
+	}
+}
+
+impl<E: RequestExecutor> From<simd_json::Error> for EmoteError<E> {
+	fn from(e: simd_json::Error) -> Self {
+		Self::Deserialize(e)
+	}
+}
+impl<E: RequestExecutor> From<http::Error> for EmoteError<E> {
+	fn from(e: http::Error) -> Self {
+		Self::BadRequest(e)
+	}
+}
+
+impl<E: RequestExecutor> fmt::Display for EmoteError<E> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::BadRequest(e) => f.write_fmt(format_args!("accidentally built malformed request: {e}")),
+			Self::Deserialize(e) => f.write_fmt(format_args!("failed to deserialize body: {e}")),
+			Self::Executor(e) => f.write_fmt(format_args!("failed to execute request: {e}")),
+			Self::Receive(e) => f.write_fmt(format_args!("failed to receive response: {e}")),
+			Self::Api { provider, status_code } => f.write_fmt(format_args!("{provider} returned status {status_code}"))
+		}
+	}
+}
+
+impl<E: RequestExecutor + fmt::Debug> StdError for EmoteError<E>
+where
+	E::Response: fmt::Debug
+{
+	fn source(&self) -> Option<&(dyn StdError + 'static)> {
+		match self {
+			Self::BadRequest(e) => Some(e),
+			Self::Deserialize(e) => Some(e),
+			Self::Executor(e) => Some(e),
+			Self::Receive(e) => Some(e),
+			Self::Api { .. } => None
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{collections::HashMap, convert::Infallible, time::Duration};
+
+	use bytes::Bytes;
+
+	use super::{EmoteProvider, EmoteUrl, ThirdPartyEmotes};
+	use crate::{
+		executor::{RequestExecutor, Response},
+		twitch::{ChatEvent, Cheermotes, MessageSegment, event::to_chat_event}
+	};
+
+	const BTTV_GLOBAL: &str = r#"[{"id":"54fa925e01e468494b85b54d","code":"OhMyGoodness","imageType":"png","animated":false,"userId":"5561169bd6b9d206222a8c19"},{"id":"5849c9a4f52be01a7ee5f79d","code":"IceCold","imageType":"png","animated":false,"userId":"5561169bd6b9d206222a8c19"}]"#;
+	const BTTV_USER: &str = r#"{"id":"5f8e4d1c9d8a8b3f0c6e6b1a","bots":[],"avatar":"","channelEmotes":[{"id":"5f8e4d1c9d8a8b3f0c6e6b1b","code":"yukiHype","imageType":"gif","animated":true,"userId":"5f8e4d1c9d8a8b3f0c6e6b1a"}],"sharedEmotes":[{"id":"5f1b0186cf6d2144653d2970","code":"catJAM","imageType":"gif","animated":true,"user":{"id":"5e8a4a5a2d6b5f1a7e8a4a5a","name":"anon","displayName":"anon","providerId":"1"}}]}"#;
+	const FFZ_GLOBAL: &str = r#"{"default_sets":[3],"sets":{"3":{"id":3,"title":"Global Emotes","emoticons":[{"id":28136,"name":"LilZ","modifier":false,"urls":{"1":"https://cdn.frankerfacez.com/emote/28136/1","4":"https://cdn.frankerfacez.com/emote/28136/4","2":"https://cdn.frankerfacez.com/emote/28136/2"}}]},"4330":{"id":4330,"title":"Special","emoticons":[{"id":1,"name":"OnlyForSomeUsers","modifier":false,"urls":{"1":"https://cdn.frankerfacez.com/emote/1/1"}}]}},"users":{"4330":["someone"]}}"#;
+	const FFZ_ROOM: &str = r#"{"room":{"_id":612045,"twitch_id":41245072,"id":"miyukiwei","set":612045},"sets":{"612045":{"id":612045,"title":"Channel: miyukiwei","emoticons":[{"id":720507,"name":"yukiPat","modifier":true,"urls":{"1":"//cdn.frankerfacez.com/emote/720507/1"}}]}}}"#;
+	const SEVENTV_GLOBAL: &str = r#"{"id":"01HKQT8EWR000ESSWF3625XCS4","name":"Global Emotes","emotes":[{"id":"60ae958e229664e8667aea38","name":"FeelsDankMan","flags":0,"data":{"id":"60ae958e229664e8667aea38","flags":0,"host":{"url":"//cdn.7tv.app/emote/60ae958e229664e8667aea38","files":[{"name":"2x.webp","format":"WEBP"},{"name":"1x.webp","format":"WEBP"},{"name":"1x.avif","format":"AVIF"}]}}},{"id":"60b0c4b4f7ea15e6ee3a3f5a","name":"DeletedEmote","flags":0,"data":null}]}"#;
+	const SEVENTV_USER: &str = r#"{"id":"01FE8D4RX800035HYBJVXYN1RB","platform":"TWITCH","username":"miyukiwei","emote_set":{"id":"01FE8D4RX800035HYBJVXYN1RC","name":"miyukiwei's Emotes","emotes":[{"id":"01GB2S4DA0000B5GN9NRKT9Y1V","name":"yukiHype","flags":1,"data":{"id":"01GB2S4DA0000B5GN9NRKT9Y1V","flags":0,"host":{"url":"//cdn.7tv.app/emote/01GB2S4DA0000B5GN9NRKT9Y1V","files":[{"name":"1x.webp","format":"WEBP"}]}}}]}}"#;
+
+	/// An executor that serves fixed responses by URL, and `404 Not Found` for any other URL.
+	#[derive(Debug)]
+	struct Providers(HashMap<&'static str, &'static str>);
+
+	impl Default for Providers {
+		fn default() -> Self {
+			Self(HashMap::from([
+				("https://api.betterttv.net/3/cached/emotes/global", BTTV_GLOBAL),
+				("https://api.betterttv.net/3/cached/users/twitch/41245072", BTTV_USER),
+				("https://api.frankerfacez.com/v1/set/global", FFZ_GLOBAL),
+				("https://api.frankerfacez.com/v1/room/id/41245072", FFZ_ROOM),
+				("https://7tv.io/v3/emote-sets/global", SEVENTV_GLOBAL),
+				("https://7tv.io/v3/users/twitch/41245072", SEVENTV_USER)
+			]))
+		}
+	}
+
+	#[derive(Debug)]
+	struct ProviderResponse(Option<&'static str>);
+
+	impl Response for ProviderResponse {
+		type Error = Infallible;
+
+		fn status_code(&self) -> u16 {
+			if self.0.is_some() { 200 } else { 404 }
+		}
+
+		async fn recv_chunk(&mut self) -> Result<Option<Bytes>, Self::Error> {
+			Ok(self.0.take().map(|body| Bytes::from_static(body.as_bytes())))
+		}
+	}
+
+	impl RequestExecutor for Providers {
+		type Response = ProviderResponse;
+		type Error = Infallible;
+
+		async fn make_request(&self, request: http::Request<Bytes>) -> Result<Self::Response, Self::Error> {
+			assert_eq!(request.method(), http::Method::GET);
+			Ok(ProviderResponse(self.0.get(request.uri().to_string().as_str()).copied()))
+		}
+
+		async fn sleep(dur: Duration) {
+			tokio::time::sleep(dur).await;
+		}
+	}
+
+	async fn loaded(emotes: ThirdPartyEmotes<Providers>) -> ThirdPartyEmotes<Providers> {
+		let mut emotes = emotes;
+		emotes.load_global().await.unwrap();
+		emotes.load_channel(41245072).await.unwrap();
+		emotes
+	}
+
+	fn text(text: &str) -> MessageSegment {
+		MessageSegment::Text { text: text.to_owned() }
+	}
+
+	/// The provider & name of each third-party emote in `segments`, or the text of other segments.
+	fn describe(segments: &[MessageSegment]) -> Vec<String> {
+		segments
+			.iter()
+			.map(|segment| match segment {
+				MessageSegment::ThirdPartyEmote { provider, name, .. } => format!("{provider}:{name}"),
+				segment => segment.to_string()
+			})
+			.collect()
+	}
+
+	#[tokio::test]
+	async fn bttv() {
+		let emotes = loaded(ThirdPartyEmotes::new(Providers::default()).with_providers([EmoteProvider::BetterTtv])).await;
+		assert_eq!(emotes.count(None), 2);
+		assert_eq!(emotes.count(Some(41245072)), 4);
+
+		let emote = emotes.get(None, "IceCold").unwrap();
+		assert_eq!(emote.id, "5849c9a4f52be01a7ee5f79d");
+		assert!(emote.zero_width);
+		assert_eq!(emote.urls.len(), 3);
+		assert_eq!(
+			emote.urls[0],
+			EmoteUrl {
+				scale: 1,
+				url: "https://cdn.betterttv.net/emote/5849c9a4f52be01a7ee5f79d/1x".to_owned()
+			}
+		);
+		assert!(!emotes.get(None, "OhMyGoodness").unwrap().zero_width);
+		assert!(emotes.get(Some(41245072), "catJAM").is_some());
+		assert!(emotes.get(None, "catJAM").is_none());
+	}
+
+	#[tokio::test]
+	async fn ffz() {
+		let emotes = loaded(ThirdPartyEmotes::new(Providers::default()).with_providers([EmoteProvider::FrankerFaceZ])).await;
+		// Sets that aren't among the default sets are only available to specific users.
+		assert!(emotes.get(None, "OnlyForSomeUsers").is_none());
+
+		let emote = emotes.get(None, "LilZ").unwrap();
+		assert_eq!(emote.id, "28136");
+		assert_eq!(emote.urls.iter().map(|url| url.scale).collect::<Vec<_>>(), [1, 2, 4]);
+
+		let emote = emotes.get(Some(41245072), "yukiPat").unwrap();
+		assert!(emote.zero_width);
+		assert_eq!(emote.urls[0].url, "https://cdn.frankerfacez.com/emote/720507/1");
+	}
+
+	#[tokio::test]
+	async fn seventv() {
+		let emotes = loaded(ThirdPartyEmotes::new(Providers::default()).with_providers([EmoteProvider::SevenTv])).await;
+		// Deleted emotes are skipped.
+		assert_eq!(emotes.count(None), 1);
+
+		let emote = emotes.get(None, "FeelsDankMan").unwrap();
+		assert!(!emote.zero_width);
+		assert_eq!(
+			emote.urls,
+			[
+				EmoteUrl {
+					scale: 1,
+					url: "https://cdn.7tv.app/emote/60ae958e229664e8667aea38/1x.webp".to_owned()
+				},
+				EmoteUrl {
+					scale: 2,
+					url: "https://cdn.7tv.app/emote/60ae958e229664e8667aea38/2x.webp".to_owned()
+				}
+			]
+		);
+		assert!(emotes.get(Some(41245072), "yukiHype").unwrap().zero_width);
+	}
+
+	#[tokio::test]
+	async fn missing_channels_have_no_emotes() {
+		let mut emotes = ThirdPartyEmotes::new(Providers::default());
+		emotes.load_channel(1).await.unwrap();
+		assert_eq!(emotes.count(Some(1)), 0);
+	}
+
+	#[tokio::test]
+	async fn provider_precedence() {
+		let emotes = loaded(ThirdPartyEmotes::new(Providers::default())).await;
+		assert_eq!(emotes.get(Some(41245072), "yukiHype").unwrap().provider, EmoteProvider::SevenTv);
+
+		let emotes = loaded(ThirdPartyEmotes::new(Providers::default()).with_providers([EmoteProvider::BetterTtv, EmoteProvider::SevenTv])).await;
+		assert_eq!(emotes.get(Some(41245072), "yukiHype").unwrap().provider, EmoteProvider::BetterTtv);
+	}
+
+	#[tokio::test]
+	async fn split_segments() {
+		let emotes = loaded(ThirdPartyEmotes::new(Providers::default())).await;
+		let segments = vec![
+			text("yukiHype hi chat LilZ"),
+			MessageSegment::Emote {
+				name: "Kappa".to_owned(),
+				id: "25".to_owned()
+			},
+			text(" catJAM"),
+		];
+		assert_eq!(
+			describe(&emotes.split_segments(Some(41245072), segments.clone())),
+			["7TV:yukiHype", " hi chat ", "FrankerFaceZ:LilZ", "Kappa", " ", "BetterTTV:catJAM"]
+		);
+		// Without a room ID, only global emotes are resolved.
+		assert_eq!(describe(&emotes.split_segments(None, segments)), ["yukiHype hi chat ", "FrankerFaceZ:LilZ", "Kappa", " catJAM"]);
+	}
+
+	#[tokio::test]
+	async fn apply() {
+		let emotes = loaded(ThirdPartyEmotes::new(Providers::default())).await;
+		let mut event = to_chat_event(
+			"@badge-info=;badges=;color=;display-name=yukifan4;emotes=;id=b34ccfc7-4977-403a-8a94-33c6bac34fb8;mod=0;room-id=41245072;tmi-sent-ts=1;user-id=1;user-type= :yukifan4!yukifan4@yukifan4.tmi.twitch.tv PRIVMSG #miyukiwei :catJAM FeelsDankMan"
+				.parse()
+				.unwrap(),
+			&Cheermotes::default()
+		)
+		.unwrap();
+		emotes.apply(&mut event);
+		let ChatEvent::Message { contents, .. } = &event else {
+			panic!("expected a message, got {event:?}");
+		};
+		assert_eq!(describe(contents), ["BetterTTV:catJAM", " ", "7TV:FeelsDankMan"]);
+	}
+}
@@ -0,0 +1,109 @@
+// Copyright 2026 pyke.io
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{error::Error as StdError, fmt};
+
+use irc::proto::{Command, Message};
+#[cfg(feature = "twitch-websocket")]
+use tokio_tungstenite::tungstenite;
+
+use super::sender::find_tag;
+
+type BoxError = Box<dyn StdError + Send + Sync>;
+
+/// An error yielded by [`Chat`](super::Chat).
+///
+/// [`Error::Banned`] and [`Error::ChannelSuspended`] only affect a single channel; the stream keeps going after
+/// yielding them. All other errors are fatal to the connection: unless [reconnection](super::Chat::with_reconnect)
+/// is enabled, the stream ends after yielding one.
+#[derive(Debug)]
+pub enum Error {
+	/// Failed to connect, or the connection failed.
+	Connection(BoxError),
+	/// The TLS handshake failed.
+	Tls(BoxError),
+	/// The server didn't respond to a keepalive `PING` in time.
+	PingTimeout,
+	/// The [identity](super::TwitchIdentity) failed to provide credentials, e.g. because a token refresh failed.
+	Credentials(BoxError),
+	/// Twitch rejected the credentials.
+	Authentication {
+		/// The human-readable explanation sent by Twitch, e.g. `Login authentication failed`.
+		message: String
+	},
+	/// The account is banned from a channel.
+	Banned { channel: String, message: String },
+	/// A channel doesn't exist or has been suspended, so it can't be joined.
+	ChannelSuspended { channel: String, message: String }
+}
+
+impl Error {
+	/// Parses a `NOTICE` telling us we can't participate in a channel.
+	pub(crate) fn from_notice(message: &Message) -> Option<Self> {
+		let Command::NOTICE(target, text) = &message.command else {
+			return None;
+		};
+		let channel = target.trim_start_matches('#').to_owned();
+		match find_tag(message, "msg-id")? {
+			"msg_banned" => Some(Self::Banned { channel, message: text.clone() }),
+			"msg_channel_suspended" => Some(Self::ChannelSuspended { channel, message: text.clone() }),
+			_ => None
+		}
+	}
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Connection(e) => f.write_fmt(format_args!("connection failed: {e}")),
+			Self::Tls(e) => f.write_fmt(format_args!("TLS error: {e}")),
+			Self::PingTimeout => f.write_str("server did not respond to ping"),
+			Self::Credentials(e) => f.write_fmt(format_args!("failed to get credentials: {e}")),
+			Self::Authentication { message } => f.write_fmt(format_args!("authentication failed: {message}")),
+			Self::Banned { channel, message } => f.write_fmt(format_args!("banned from #{channel}: {message}")),
+			Self::ChannelSuspended { channel, message } => f.write_fmt(format_args!("cannot join #{channel}: {message}"))
+		}
+	}
+}
+
+impl StdError for Error {
+	fn source(&self) -> Option<&(dyn StdError + 'static)> {
+		match self {
+			Self::Connection(e) | Self::Tls(e) | Self::Credentials(e) => Some(e.as_ref()),
+			_ => None
+		}
+	}
+}
+
+impl From<irc::error::Error> for Error {
+	fn from(e: irc::error::Error) -> Self {
+		match e {
+			#[cfg(any(feature = "tls-native", feature = "tls-rust"))]
+			irc::error::Error::Tls(e) => Self::Tls(Box::new(e)),
+			irc::error::Error::PingTimeout => Self::PingTimeout,
+			e => Self::Connection(Box::new(e))
+		}
+	}
+}
+
+#[cfg(feature = "twitch-websocket")]
+impl From<tungstenite::Error> for Error {
+	fn from(e: tungstenite::Error) -> Self {
+		match e {
+			#[cfg(any(feature = "tls-native", feature = "tls-rust"))]
+			tungstenite::Error::Tls(e) => Self::Tls(Box::new(e)),
+			e => Self::Connection(Box::new(e))
+		}
+	}
+}
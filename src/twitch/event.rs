@@ -18,9 +18,12 @@ use std::{
 	num::{NonZeroU16, NonZeroU32}
 };
 
-use irc::proto::{Command, Response};
+use irc::proto::{Command, Response, message::Tag};
 use uuid::Uuid;
 
+#[cfg(feature = "twitch-emotes")]
+use super::emotes::{EmoteProvider, EmoteUrl};
+use super::{Cheermotes, DisconnectReason, SendRejection};
 use crate::util::{MapNonempty, get_utf8_slice};
 
 /// A user's role.
@@ -38,6 +41,56 @@ pub enum UserRole {
 	TwitchStaff
 }
 
+/// A chat badge displayed next to a user's name.
+///
+/// See the [Get Global Chat Badges](https://dev.twitch.tv/docs/api/reference/#get-global-chat-badges) API for badge
+/// images.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Badge {
+	/// The badge's set ID, e.g. `subscriber`, `vip` or `bits`.
+	pub set_id: String,
+	/// The badge's version within its set. For `bits`, this is the Bits tier, e.g. `1000`. For `subscriber`, this
+	/// encodes both the tier and the month bracket, e.g. `3012` for a Tier 3 subscriber of 12 or more months.
+	pub version: String,
+	/// Additional metadata from the `badge-info` tag, if any. For `subscriber` and `founder`, this is the exact number
+	/// of months the user has been subscribed; for `predictions`, the name of the outcome the user predicted.
+	pub info: Option<String>
+}
+
+/// Flags derived from a user's badges.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UserFlags {
+	/// User is a VIP in this channel.
+	pub vip: bool,
+	/// User is subscribed to this channel. Founders are also subscribers.
+	pub subscriber: bool,
+	/// User is one of the channel's first subscribers.
+	pub founder: bool,
+	/// User is an artist for this channel (i.e. they contributed emotes or other art).
+	pub artist: bool
+}
+
+impl UserFlags {
+	fn from_badges(badges: &[Badge]) -> Self {
+		let mut flags = Self::default();
+		for badge in badges {
+			match badge.set_id.as_str() {
+				"vip" => flags.vip = true,
+				"subscriber" => flags.subscriber = true,
+				"founder" => {
+					flags.founder = true;
+					flags.subscriber = true;
+				}
+				"artist-badge" => flags.artist = true,
+				_ => {}
+			}
+		}
+		flags
+	}
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct User {
@@ -55,10 +108,98 @@ pub struct User {
 	pub sub_months: Option<NonZeroU16>,
 	/// The user's role.
 	pub role: UserRole,
+	/// The user's badges, in the order they are displayed.
+	pub badges: Vec<Badge>,
+	/// Flags derived from the user's badges.
+	pub flags: UserFlags,
 	/// Whether or not the user is a "returning" chatter in this channel.
 	pub returning_chatter: bool
 }
 
+impl User {
+	/// Returns the user's badge from the given set, e.g. `bits` or `predictions`, if they have one.
+	pub fn badge(&self, set_id: &str) -> Option<&Badge> {
+		self.badges.iter().find(|badge| badge.set_id == set_id)
+	}
+}
+
+/// A special effect applied to a message, typically by redeeming a channel points reward or a Bits power-up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MessageEffect {
+	/// The message was highlighted via the "Highlight My Message" channel points reward.
+	Highlighted,
+	/// The message was sent via the "Send a Message in Sub-Only Mode" channel points reward.
+	SkipSubsMode,
+	/// The last emote in the message was enlarged via the "Gigantify an Emote" power-up.
+	GigantifiedEmote,
+	/// The message was sent with an animated effect via the "Message Effects" power-up.
+	Animated {
+		/// The ID of the effect, e.g. `simmer`, `rainbow-eclipse` or `cosmic-abyss`.
+		animation_id: String
+	},
+	/// Any other effect, containing the raw `msg-id`.
+	Other(String)
+}
+
+impl MessageEffect {
+	fn from_tags(tags: &mut Tags) -> Option<Self> {
+		let msg_id = tags.remove("msg-id")?;
+		Some(match msg_id.as_str() {
+			"highlighted-message" => Self::Highlighted,
+			"skip-subs-mode-message" => Self::SkipSubsMode,
+			"gigantified-emote-message" => Self::GigantifiedEmote,
+			"animated-message" => Self::Animated {
+				animation_id: tags.remove("animation-id").unwrap_or_default()
+			},
+			_ => Self::Other(msg_id)
+		})
+	}
+}
+
+/// Describes the message a reply was sent in response to.
+///
+/// Twitch prefixes the text of a reply with `@parent_username`; that mention is left in the reply's contents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReplyInfo {
+	/// The ID of the message being replied to.
+	pub parent_id: Uuid,
+	/// The channel ID of the parent message's author.
+	pub parent_user_id: Option<u64>,
+	pub parent_username: String,
+	pub parent_display_name: String,
+	/// The text of the parent message.
+	pub parent_message: String,
+	/// The ID of the message that started the thread, which is the same as `parent_id` when replying to a message that
+	/// isn't itself a reply.
+	pub thread_id: Uuid,
+	/// The username of the thread's author.
+	pub thread_username: Option<String>
+}
+
+impl ReplyInfo {
+	fn from_tags(tags: &mut Tags) -> Option<Self> {
+		let parent_id: Uuid = tags.remove("reply-parent-msg-id")?.parse().ok()?;
+		let parent_username = tags.remove("reply-parent-user-login").unwrap_or_default();
+		Some(ReplyInfo {
+			parent_id,
+			parent_user_id: tags.remove("reply-parent-user-id").and_then(|f| f.parse().ok()),
+			parent_display_name: tags
+				.remove("reply-parent-display-name")
+				.filter(|f| !f.is_empty())
+				.unwrap_or_else(|| parent_username.clone()),
+			parent_username,
+			parent_message: tags.remove("reply-parent-msg-body").unwrap_or_default(),
+			thread_id: tags
+				.remove("reply-thread-parent-msg-id")
+				.and_then(|f| f.parse().ok())
+				.unwrap_or(parent_id),
+			thread_username: tags.remove("reply-thread-parent-user-login")
+		})
+	}
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(tag = "t"))]
@@ -78,6 +219,28 @@ pub enum MessageSegment {
 	Emote {
 		name: String,
 		id: String
+	},
+	/// A cheer, e.g. `Cheer100`, in a message containing Bits. Only produced for prefixes recognized by the
+	/// [`Cheermotes`](super::Cheermotes) set of the [`Chat`](super::Chat).
+	///
+	/// Images for the cheermote can be found via the [Get Cheermotes](https://dev.twitch.tv/docs/api/reference/#get-cheermotes)
+	/// API, using the tier with the highest `min_bits` less than or equal to `amount`.
+	Cheer {
+		/// The cheermote's prefix as written in the message, e.g. `Cheer` or `Kappa`.
+		prefix: String,
+		amount: NonZeroU32
+	},
+	/// An emote from a third-party provider like BetterTTV or 7TV. These are sent as plain text, and are only produced
+	/// by [`ThirdPartyEmotes`](super::emotes::ThirdPartyEmotes) for emotes it has loaded.
+	#[cfg(feature = "twitch-emotes")]
+	ThirdPartyEmote {
+		provider: EmoteProvider,
+		id: String,
+		name: String,
+		/// The emote's images, from smallest to largest.
+		urls: Vec<EmoteUrl>,
+		/// Whether the emote is meant to be drawn over the previous emote rather than next to it.
+		zero_width: bool
 	}
 }
 
@@ -85,7 +248,268 @@ impl fmt::Display for MessageSegment {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		match self {
 			Self::Text { text } => f.write_str(text),
-			Self::Emote { name, .. } => f.write_str(name)
+			Self::Emote { name, .. } => f.write_str(name),
+			#[cfg(feature = "twitch-emotes")]
+			Self::ThirdPartyEmote { name, .. } => f.write_str(name),
+			Self::Cheer { prefix, amount } => f.write_fmt(format_args!("{prefix}{amount}"))
+		}
+	}
+}
+
+/// The tier of a subscription.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SubTier {
+	/// A Prime Gaming subscription, equivalent to [`SubTier::Tier1`].
+	Prime,
+	Tier1,
+	Tier2,
+	Tier3
+}
+
+impl SubTier {
+	pub(crate) fn from_plan(plan: &str) -> Option<Self> {
+		match plan {
+			"Prime" => Some(Self::Prime),
+			"1000" => Some(Self::Tier1),
+			"2000" => Some(Self::Tier2),
+			"3000" => Some(Self::Tier3),
+			_ => None
+		}
+	}
+}
+
+/// The recipient of a gifted subscription.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GiftRecipient {
+	pub username: String,
+	pub display_name: String,
+	/// The recipient's channel ID, for use via the Twitch API.
+	pub id: u64
+}
+
+/// The accent color of an announcement, as chosen by the moderator who sent it.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AnnouncementColor {
+	/// The channel's accent color.
+	#[default]
+	Primary,
+	Blue,
+	Green,
+	Orange,
+	Purple,
+	Other(String)
+}
+
+impl AnnouncementColor {
+	pub(crate) fn from_param(color: &str) -> Self {
+		match color {
+			"PRIMARY" => Self::Primary,
+			"BLUE" => Self::Blue,
+			"GREEN" => Self::Green,
+			"ORANGE" => Self::Orange,
+			"PURPLE" => Self::Purple,
+			x => Self::Other(x.to_owned())
+		}
+	}
+}
+
+/// The connected account's state, as reported by Twitch upon joining a channel (`USERSTATE`) or logging in
+/// (`GLOBALUSERSTATE`).
+///
+/// The latest states are cached by [`Chat`](super::Chat); see [`Chat::user_state`](super::Chat::user_state) and
+/// [`Chat::global_user_state`](super::Chat::global_user_state).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UserState {
+	pub display_name: String,
+	/// The account's chosen name color, as an RGB value, or `None` if it hasn't chosen one.
+	pub display_color: Option<u32>,
+	pub role: UserRole,
+	pub badges: Vec<Badge>,
+	pub flags: UserFlags,
+	/// The IDs of the emote sets the account can use, e.g. from subscriptions. Emote set info can be fetched with the
+	/// [Get Emote Sets](https://dev.twitch.tv/docs/api/reference/#get-emote-sets) API.
+	pub emote_sets: Vec<String>
+}
+
+impl UserState {
+	/// Whether the account can moderate the channel, i.e. it is the broadcaster or one of its moderators.
+	pub fn is_moderator(&self) -> bool {
+		matches!(self.role, UserRole::Broadcaster | UserRole::Moderator)
+	}
+
+	/// Whether the account is a VIP in the channel.
+	pub fn is_vip(&self) -> bool {
+		self.flags.vip
+	}
+
+	fn from_tags(tags: &mut Tags) -> Self {
+		let badges = parse_badges(tags.remove("badges"), tags.remove("badge-info"));
+		Self {
+			display_name: tags.remove("display-name").unwrap_or_default(),
+			display_color: tags.remove("color").and_then_nonempty(|c| parse_color(&c)),
+			role: parse_role(tags, &badges),
+			flags: UserFlags::from_badges(&badges),
+			badges,
+			emote_sets: tags
+				.remove("emote-sets")
+				.map(|sets| sets.split(',').filter(|set| !set.is_empty()).map(str::to_owned).collect())
+				.unwrap_or_default()
+		}
+	}
+}
+
+/// The origin of a message or event received during a [shared chat](https://help.twitch.tv/s/article/shared-chat)
+/// session, in which the chats of multiple channels are combined.
+///
+/// Messages sent in any channel of a session are received in every channel of the session joined by the client, each
+/// with the same `message_id`. Messages sent in the channel they're received in also carry a source; compare
+/// `room_id` against [`Channel::room_id`] to tell local chatters from partner chatters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SharedChatSource {
+	/// The ID of the channel the message or event originated in.
+	pub room_id: u64,
+	/// The ID of the message in the channel it originated in.
+	pub message_id: Uuid,
+	/// The user's badges in the channel the message originated in. [`User::badges`] contains the user's badges in the
+	/// channel the message was received in.
+	pub badges: Vec<Badge>
+}
+
+impl SharedChatSource {
+	fn from_tags(tags: &mut Tags) -> Option<Self> {
+		let room_id = tags.remove("source-room-id").and_then(|f| f.parse().ok());
+		let message_id = tags.remove("source-id").and_then(|f| f.parse().ok());
+		let badges = parse_badges(tags.remove("source-badges"), tags.remove("source-badge-info"));
+		Some(Self {
+			room_id: room_id?,
+			message_id: message_id?,
+			badges
+		})
+	}
+
+	/// Returns whether the message or event originated in `channel`.
+	pub fn is_from(&self, channel: &Channel) -> bool {
+		channel.room_id == Some(self.room_id)
+	}
+}
+
+/// The kind of a [`ChatEvent::Notice`], parsed from its `msg-id` tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NoticeKind {
+	/// A message was rejected by the channel. Rejections of messages sent through a [`ChatSender`](super::ChatSender)
+	/// are also reported to the sender.
+	MessageRejected(SendRejection),
+	/// A chat command like `/ban` was sent over IRC, which Twitch no longer supports; use the [Helix API](super::helix)
+	/// instead.
+	UnrecognizedCommand,
+	/// The account doesn't have permission to perform an action.
+	NoPermission,
+	/// Any other notice, containing the raw `msg-id`.
+	Other(String)
+}
+
+impl NoticeKind {
+	pub(crate) fn from_msg_id(msg_id: &str) -> Self {
+		match msg_id {
+			"unrecognized_cmd" => Self::UnrecognizedCommand,
+			"no_permission" => Self::NoPermission,
+			x if x.starts_with("msg_") => Self::MessageRejected(SendRejection::from_msg_id(x)),
+			x => Self::Other(x.to_owned())
+		}
+	}
+}
+
+/// The channel an event was received in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Channel {
+	/// The channel's name, i.e. the broadcaster's username, without the leading `#`.
+	pub name: String,
+	/// The channel's ID, for use via the Twitch API. This is only `None` for events received before the channel's
+	/// initial [`ChatEvent::RoomState`].
+	pub room_id: Option<u64>
+}
+
+impl Channel {
+	pub(crate) fn from_name(target: &str) -> Self {
+		Self {
+			name: target.trim_start_matches('#').to_owned(),
+			room_id: None
+		}
+	}
+
+	fn from_target(target: &str, tags: &mut Tags) -> Self {
+		Self {
+			name: target.trim_start_matches('#').to_owned(),
+			room_id: tags.remove("room-id").and_then(|f| f.parse().ok())
+		}
+	}
+}
+
+/// Whether a channel's chat is restricted to followers.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FollowersOnly {
+	#[default]
+	Disabled,
+	/// Only users who have followed the channel for at least `min_follow_minutes` minutes can chat. A value of `0`
+	/// allows all followers to chat.
+	Enabled { min_follow_minutes: u32 }
+}
+
+/// A change to a channel's chat settings. Fields which did not change are `None`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RoomStateUpdate {
+	pub emote_only: Option<bool>,
+	pub followers_only: Option<FollowersOnly>,
+	pub subs_only: Option<bool>,
+	/// Whether unique-chat (aka r9k) mode is enabled, which prevents users from sending duplicate messages.
+	pub unique_chat: Option<bool>,
+	/// The number of seconds users must wait between sending messages. A value of `0` means slow mode is disabled.
+	pub slow_mode_secs: Option<u32>
+}
+
+/// The current chat settings for a channel, obtained via [`Chat::room_state`](super::Chat::room_state).
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RoomState {
+	/// The channel's ID, for use via the Twitch API. This is `None` until the first `ROOMSTATE` is received.
+	pub room_id: Option<u64>,
+	pub emote_only: bool,
+	pub followers_only: FollowersOnly,
+	pub subs_only: bool,
+	/// Whether unique-chat (aka r9k) mode is enabled, which prevents users from sending duplicate messages.
+	pub unique_chat: bool,
+	/// The number of seconds users must wait between sending messages. A value of `0` means slow mode is disabled.
+	pub slow_mode_secs: u32
+}
+
+impl RoomState {
+	pub(crate) fn apply(&mut self, room_id: Option<u64>, update: &RoomStateUpdate) {
+		if room_id.is_some() {
+			self.room_id = room_id;
+		}
+		if let Some(emote_only) = update.emote_only {
+			self.emote_only = emote_only;
+		}
+		if let Some(followers_only) = update.followers_only {
+			self.followers_only = followers_only;
+		}
+		if let Some(subs_only) = update.subs_only {
+			self.subs_only = subs_only;
+		}
+		if let Some(unique_chat) = update.unique_chat {
+			self.unique_chat = unique_chat;
+		}
+		if let Some(slow_mode_secs) = update.slow_mode_secs {
+			self.slow_mode_secs = slow_mode_secs;
 		}
 	}
 }
@@ -93,167 +517,1219 @@ impl fmt::Display for MessageSegment {
 #[derive(Debug)]
 pub enum ChatEvent {
 	Message {
+		channel: Channel,
 		id: Uuid,
 		user: User,
 		sent_at_ms: i64,
-		reply_to: Option<Uuid>,
+		shared_chat: Option<SharedChatSource>,
+		/// If this message is a reply, describes the message it replies to and the thread it belongs to.
+		reply_to: Option<ReplyInfo>,
+		/// If the message was sent by redeeming a custom channel points reward that requires the viewer to enter text,
+		/// the ID of the reward. The redemption itself is only available via
+		/// [EventSub](super::eventsub::Subscription::channel_points_redemption).
+		reward_id: Option<Uuid>,
+		/// Any special effect applied to the message.
+		effect: Option<MessageEffect>,
 		emote_only: bool,
 		first_message: bool,
+		/// Whether the message was sent with `/me`. Twitch clients typically display these in italics or in the user's
+		/// color.
+		is_action: bool,
 		contents: Vec<MessageSegment>
 	},
 	SendBits {
+		channel: Channel,
 		id: Uuid,
 		user: User,
 		bits: NonZeroU32,
 		sent_at_ms: i64,
+		shared_chat: Option<SharedChatSource>,
+		/// If this message is a reply, describes the message it replies to and the thread it belongs to.
+		reply_to: Option<ReplyInfo>,
+		first_message: bool,
+		/// Whether the message was sent with `/me`.
+		is_action: bool,
 		segments: Vec<MessageSegment>
 	},
+	/// A user subscribed to the channel for the first time.
+	///
+	/// `system_message` is the message Twitch displays for the event, e.g. `"yukifan4 subscribed with Prime."`, and
+	/// `message` is the optional message the user shared along with it.
+	Subscription {
+		channel: Channel,
+		id: Uuid,
+		user: User,
+		sent_at_ms: i64,
+		shared_chat: Option<SharedChatSource>,
+		tier: SubTier,
+		system_message: String,
+		message: Option<Vec<MessageSegment>>
+	},
+	/// A user renewed their subscription to the channel.
+	Resubscription {
+		channel: Channel,
+		id: Uuid,
+		user: User,
+		sent_at_ms: i64,
+		shared_chat: Option<SharedChatSource>,
+		tier: SubTier,
+		/// The total number of months the user has been subscribed.
+		cumulative_months: NonZeroU16,
+		/// The number of consecutive months the user has been subscribed, if they chose to share it.
+		streak_months: Option<NonZeroU16>,
+		system_message: String,
+		message: Option<Vec<MessageSegment>>
+	},
+	/// `user` gifted a subscription to another user.
+	///
+	/// Gifts sent anonymously are attributed to the `ananonymousgifter` user.
+	SubGift {
+		channel: Channel,
+		id: Uuid,
+		user: User,
+		sent_at_ms: i64,
+		shared_chat: Option<SharedChatSource>,
+		tier: SubTier,
+		recipient: GiftRecipient,
+		/// The total number of months the recipient has been subscribed.
+		recipient_months: NonZeroU16,
+		/// The number of months gifted, for multi-month gifts.
+		gift_months: Option<NonZeroU16>,
+		/// The total number of subscriptions `user` has gifted in this channel, if they chose to share it.
+		sender_total: Option<u32>,
+		system_message: String
+	},
+	/// `user` gifted subscriptions to multiple random users in the channel at once. The individual gifts follow as
+	/// [`ChatEvent::SubGift`] events.
+	CommunityGift {
+		channel: Channel,
+		id: Uuid,
+		user: User,
+		sent_at_ms: i64,
+		shared_chat: Option<SharedChatSource>,
+		tier: SubTier,
+		/// The number of subscriptions gifted.
+		count: NonZeroU32,
+		/// The total number of subscriptions `user` has gifted in this channel, if they chose to share it.
+		sender_total: Option<u32>,
+		system_message: String
+	},
+	/// `user` upgraded a gifted subscription to a paid subscription. The gifter's names are `None` if the original
+	/// gift was anonymous.
+	GiftUpgrade {
+		channel: Channel,
+		id: Uuid,
+		user: User,
+		sent_at_ms: i64,
+		shared_chat: Option<SharedChatSource>,
+		gifter_username: Option<String>,
+		gifter_display_name: Option<String>,
+		system_message: String
+	},
+	/// `user` upgraded their Prime Gaming subscription to a paid subscription.
+	PrimeUpgrade {
+		channel: Channel,
+		id: Uuid,
+		user: User,
+		sent_at_ms: i64,
+		shared_chat: Option<SharedChatSource>,
+		tier: SubTier,
+		system_message: String
+	},
+	/// `user` raided the channel.
+	Raid {
+		channel: Channel,
+		id: Uuid,
+		user: User,
+		sent_at_ms: i64,
+		shared_chat: Option<SharedChatSource>,
+		/// The number of viewers joining from the raid.
+		viewer_count: u32,
+		system_message: String
+	},
+	/// A moderator sent an announcement using `/announce`.
+	Announcement {
+		channel: Channel,
+		id: Uuid,
+		user: User,
+		sent_at_ms: i64,
+		shared_chat: Option<SharedChatSource>,
+		color: AnnouncementColor,
+		system_message: String,
+		contents: Vec<MessageSegment>
+	},
+	/// `user` reached a new tier of the Bits badge.
+	BitsBadgeTier {
+		channel: Channel,
+		id: Uuid,
+		user: User,
+		sent_at_ms: i64,
+		shared_chat: Option<SharedChatSource>,
+		/// The number of Bits required for the new badge tier, e.g. `1000` or `5000`.
+		threshold: u32,
+		system_message: String,
+		message: Option<Vec<MessageSegment>>
+	},
+	/// A moderator deleted a single message.
+	///
+	/// Twitch doesn't include the author's user ID for deleted messages, so caches should be keyed on the message
+	/// `id` instead.
+	MessageDeleted {
+		channel: Channel,
+		/// The ID of the deleted message.
+		id: Uuid,
+		/// The username of the message's author.
+		username: String,
+		/// The text of the deleted message.
+		contents: String,
+		sent_at_ms: i64
+	},
+	/// A moderator timed out a user, deleting all of their messages.
+	UserTimedOut {
+		channel: Channel,
+		username: String,
+		user_id: u64,
+		/// How long the user is timed out for, in seconds.
+		duration_secs: u32,
+		sent_at_ms: i64
+	},
+	/// A moderator permanently banned a user, deleting all of their messages.
+	UserBanned {
+		channel: Channel,
+		username: String,
+		user_id: u64,
+		sent_at_ms: i64
+	},
+	/// A moderator cleared all messages from the chat using `/clear`.
+	ChatCleared {
+		channel: Channel,
+		sent_at_ms: i64
+	},
+	/// The channel's chat settings changed. A full update containing every setting is sent upon joining the channel.
+	RoomState {
+		channel: Channel,
+		update: RoomStateUpdate
+	},
+	/// The connected account's state in a channel. Sent upon joining the channel, and after each message the account
+	/// sends to it.
+	UserState {
+		channel: Channel,
+		state: UserState,
+		/// The ID of the message this state was sent in response to, if any.
+		message_id: Option<Uuid>
+	},
+	/// The connected account's global state, sent after logging in with an [`Authenticated`](super::Authenticated)
+	/// identity.
+	GlobalUserState {
+		user_id: u64,
+		state: UserState
+	},
 	MemberChunk {
+		channel: Channel,
 		names: Vec<String>
 	},
-	EndOfMembers
+	EndOfMembers {
+		channel: Channel
+	},
+	/// A user joined a channel. Requires the [`membership`](super::Capabilities::membership) capability.
+	///
+	/// Twitch sends these in batches every few seconds, and not at all in channels with more than 1,000 chatters. This
+	/// event is also received for the connected account itself upon joining a channel.
+	Join {
+		channel: Channel,
+		username: String
+	},
+	/// A user left a channel. Requires the [`membership`](super::Capabilities::membership) capability; see
+	/// [`ChatEvent::Join`].
+	Part {
+		channel: Channel,
+		username: String
+	},
+	/// A private message sent to the connected account. Use
+	/// [`helix::Client::send_whisper`](super::helix::Client::send_whisper) to reply.
+	Whisper {
+		user: User,
+		/// The name of the account the whisper was sent to, i.e. the connected account.
+		recipient: String,
+		/// Identifies the conversation between the two users, in the form `<lower user ID>_<higher user ID>`.
+		thread_id: String,
+		/// The whisper's ID. Unlike chat message IDs, this is a sequential number rather than a UUID.
+		message_id: String,
+		contents: Vec<MessageSegment>
+	},
+	/// An informational message from Twitch, e.g. explaining why a message was rejected.
+	///
+	/// Notices that prevent the account from participating in a channel are instead yielded as
+	/// [`Error::Banned`](super::Error::Banned) or [`Error::ChannelSuspended`](super::Error::ChannelSuspended) by
+	/// [`Chat`](super::Chat), unless they were sent in response to a message sent through a
+	/// [`ChatSender`](super::ChatSender).
+	Notice {
+		/// The channel the notice relates to, or `None` if it relates to the connection as a whole.
+		channel: Option<Channel>,
+		/// The kind of notice, or `None` if Twitch didn't specify one (e.g. for login failures).
+		kind: Option<NoticeKind>,
+		/// The human-readable message.
+		message: String
+	},
+	/// The connection was lost. Only emitted when reconnection is enabled via
+	/// [`Chat::with_reconnect`](super::Chat::with_reconnect); the stream will attempt to reconnect and yield
+	/// [`ChatEvent::Reconnected`] once it succeeds.
+	///
+	/// Any events sent while disconnected are lost.
+	Disconnected {
+		reason: DisconnectReason
+	},
+	/// A new connection was established after a [`ChatEvent::Disconnected`], and all channels are being re-joined.
+	Reconnected {
+		/// The number of attempts it took to reconnect.
+		attempts: u32
+	}
 }
 
-pub(crate) fn to_chat_event(message: irc::proto::Message) -> Option<ChatEvent> {
-	match message.command {
-		Command::PRIVMSG(_, msg) => {
-			let mut tags = message
-				.tags?
-				.into_iter()
-				.filter(|c| c.1.is_some())
-				.map(|c| (c.0, c.1.expect("infallible")))
-				.collect::<HashMap<_, _>>();
-
-			let (username, user_display_name) = match message.prefix? {
-				irc::proto::Prefix::Nickname(n1, n2, _) => (
-					n1,
-					match tags.remove("display-name") {
-						Some(display_name) => {
-							if display_name.is_empty() {
-								n2
-							} else {
-								display_name
-							}
-						}
-						None => n2
-					}
-				),
-				_ => return None
-			};
+impl ChatEvent {
+	/// Returns the channel this event was received in.
+	pub fn channel(&self) -> Option<&Channel> {
+		match self {
+			Self::Message { channel, .. }
+			| Self::SendBits { channel, .. }
+			| Self::Subscription { channel, .. }
+			| Self::Resubscription { channel, .. }
+			| Self::SubGift { channel, .. }
+			| Self::CommunityGift { channel, .. }
+			| Self::GiftUpgrade { channel, .. }
+			| Self::PrimeUpgrade { channel, .. }
+			| Self::Raid { channel, .. }
+			| Self::Announcement { channel, .. }
+			| Self::BitsBadgeTier { channel, .. }
+			| Self::MessageDeleted { channel, .. }
+			| Self::UserTimedOut { channel, .. }
+			| Self::UserBanned { channel, .. }
+			| Self::ChatCleared { channel, .. }
+			| Self::RoomState { channel, .. }
+			| Self::UserState { channel, .. }
+			| Self::MemberChunk { channel, .. }
+			| Self::EndOfMembers { channel, .. }
+			| Self::Join { channel, .. }
+			| Self::Part { channel, .. } => Some(channel),
+			Self::Notice { channel, .. } => channel.as_ref(),
+			Self::GlobalUserState { .. } | Self::Whisper { .. } | Self::Disconnected { .. } | Self::Reconnected { .. } => None
+		}
+	}
+
+	/// Returns the segments of the message included with this event, if any.
+	pub fn segments(&self) -> Option<&[MessageSegment]> {
+		match self {
+			Self::Message { contents, .. } | Self::Announcement { contents, .. } | Self::Whisper { contents, .. } => Some(contents),
+			Self::SendBits { segments, .. } => Some(segments),
+			Self::Subscription { message, .. } | Self::Resubscription { message, .. } | Self::BitsBadgeTier { message, .. } => message.as_deref(),
+			_ => None
+		}
+	}
+
+	/// Returns the segments of the message included with this event mutably, if any.
+	pub fn segments_mut(&mut self) -> Option<&mut Vec<MessageSegment>> {
+		match self {
+			Self::Message { contents, .. } | Self::Announcement { contents, .. } | Self::Whisper { contents, .. } => Some(contents),
+			Self::SendBits { segments, .. } => Some(segments),
+			Self::Subscription { message, .. } | Self::Resubscription { message, .. } | Self::BitsBadgeTier { message, .. } => message.as_mut(),
+			_ => None
+		}
+	}
+
+	/// Returns where this event originated, if it was received during a shared chat session.
+	pub fn shared_chat(&self) -> Option<&SharedChatSource> {
+		match self {
+			Self::Message { shared_chat, .. }
+			| Self::SendBits { shared_chat, .. }
+			| Self::Subscription { shared_chat, .. }
+			| Self::Resubscription { shared_chat, .. }
+			| Self::SubGift { shared_chat, .. }
+			| Self::CommunityGift { shared_chat, .. }
+			| Self::GiftUpgrade { shared_chat, .. }
+			| Self::PrimeUpgrade { shared_chat, .. }
+			| Self::Raid { shared_chat, .. }
+			| Self::Announcement { shared_chat, .. }
+			| Self::BitsBadgeTier { shared_chat, .. } => shared_chat.as_ref(),
+			_ => None
+		}
+	}
+
+	pub(crate) fn channel_mut(&mut self) -> Option<&mut Channel> {
+		match self {
+			Self::Message { channel, .. }
+			| Self::SendBits { channel, .. }
+			| Self::Subscription { channel, .. }
+			| Self::Resubscription { channel, .. }
+			| Self::SubGift { channel, .. }
+			| Self::CommunityGift { channel, .. }
+			| Self::GiftUpgrade { channel, .. }
+			| Self::PrimeUpgrade { channel, .. }
+			| Self::Raid { channel, .. }
+			| Self::Announcement { channel, .. }
+			| Self::BitsBadgeTier { channel, .. }
+			| Self::MessageDeleted { channel, .. }
+			| Self::UserTimedOut { channel, .. }
+			| Self::UserBanned { channel, .. }
+			| Self::ChatCleared { channel, .. }
+			| Self::RoomState { channel, .. }
+			| Self::UserState { channel, .. }
+			| Self::MemberChunk { channel, .. }
+			| Self::EndOfMembers { channel, .. }
+			| Self::Join { channel, .. }
+			| Self::Part { channel, .. } => Some(channel),
+			Self::Notice { channel, .. } => channel.as_mut(),
+			Self::GlobalUserState { .. } | Self::Whisper { .. } | Self::Disconnected { .. } | Self::Reconnected { .. } => None
+		}
+	}
+}
+
+type Tags = HashMap<String, String>;
 
-			let mut badges = tags
-				.remove("badges")
-				.and_then_nonempty(|c| {
-					c.split(',')
-						.map(|f| {
-							let mut split = f.splitn(2, '/');
-							Some((split.next()?.to_owned(), split.next()?.to_owned()))
-						})
-						.collect::<Option<HashMap<_, _>>>()
+fn collect_tags(tags: Option<Vec<Tag>>) -> Option<Tags> {
+	Some(tags?.into_iter().filter_map(|Tag(key, value)| Some((key, value?))).collect())
+}
+
+fn parse_user(username: String, fallback_display_name: String, tags: &mut Tags) -> Option<User> {
+	let display_name = match tags.remove("display-name") {
+		Some(display_name) => {
+			if display_name.is_empty() {
+				fallback_display_name
+			} else {
+				display_name
+			}
+		}
+		None => fallback_display_name
+	};
+
+	let badges = parse_badges(tags.remove("badges"), tags.remove("badge-info"));
+
+	let color = tags.remove("color").and_then_nonempty(|c| parse_color(&c));
+
+	Some(User {
+		username,
+		display_name,
+		display_color: color,
+		role: parse_role(tags, &badges),
+		returning_chatter: matches!(tags.remove("returning-chatter").as_deref(), Some("1")),
+		sub_months: badges
+			.iter()
+			.find(|badge| matches!(badge.set_id.as_str(), "subscriber" | "founder"))
+			.and_then(|badge| badge.info.as_deref()?.parse().ok()),
+		flags: UserFlags::from_badges(&badges),
+		badges,
+		id: tags.remove("user-id").and_then(|f| f.parse().ok())?
+	})
+}
+
+fn parse_role(tags: &mut Tags, badges: &[Badge]) -> UserRole {
+	match tags.remove("user-type").as_deref() {
+		Some("admin") => UserRole::TwitchAdmin,
+		Some("global_mod") => UserRole::GlobalModerator,
+		Some("staff") => UserRole::TwitchStaff,
+		_ => match tags.remove("mod").as_deref() {
+			Some("1") => UserRole::Moderator,
+			_ if badges.iter().any(|badge| badge.set_id == "broadcaster") => UserRole::Broadcaster,
+			_ => UserRole::Normal
+		}
+	}
+}
+
+/// Parses a `color` tag of the form `#RRGGBB`.
+fn parse_color(color: &str) -> Option<u32> {
+	u32::from_str_radix(color.strip_prefix('#')?, 16).ok()
+}
+
+/// Parses the `badges` and `badge-info` tags.
+fn parse_badges(badges: Option<String>, badge_info: Option<String>) -> Vec<Badge> {
+	let mut badge_info = badge_info
+		.and_then_nonempty(|c| {
+			c.split(',')
+				.map(|f| {
+					let mut split = f.splitn(2, '/');
+					Some((split.next()?.to_owned(), split.next()?.to_owned()))
 				})
-				.unwrap_or_default();
-			let mut badge_info = tags
-				.remove("badge-info")
-				.and_then_nonempty(|c| {
-					c.split(',')
-						.map(|f| {
-							let mut split = f.splitn(2, '/');
-							Some((split.next()?.to_owned(), split.next()?.to_owned()))
-						})
-						.collect::<Option<HashMap<_, _>>>()
+				.collect::<Option<HashMap<_, _>>>()
+		})
+		.unwrap_or_default();
+	badges
+		.and_then_nonempty(|c| {
+			c.split(',')
+				.map(|f| {
+					let mut split = f.splitn(2, '/');
+					let set_id = split.next()?.to_owned();
+					let version = split.next()?.to_owned();
+					let info = badge_info.remove(&set_id);
+					Some(Badge { set_id, version, info })
 				})
-				.unwrap_or_default();
+				.collect::<Option<Vec<_>>>()
+		})
+		.unwrap_or_default()
+}
 
-			let color = tags.remove("color").and_then_nonempty(|c| u32::from_str_radix(&c[1..], 16).ok());
+/// Strips the CTCP `ACTION` wrapper from messages sent with `/me`, returning whether the message was an action.
+fn strip_action(msg: String) -> (String, bool) {
+	match msg.strip_prefix("\u{1}ACTION ") {
+		Some(action) => (action.strip_suffix('\u{1}').unwrap_or(action).to_owned(), true),
+		None => (msg, false)
+	}
+}
 
-			let mut emotes = vec![];
-			for emote in tags.remove("emotes")?.split('/') {
-				if emote.is_empty() {
-					break;
-				}
+fn parse_segments(msg: String, emotes: &str) -> Option<Vec<MessageSegment>> {
+	let mut ranges = vec![];
+	for emote in emotes.split('/') {
+		if emote.is_empty() {
+			break;
+		}
 
-				let mut split = emote.splitn(2, ':');
-				let (id, ranges) = (split.next()?, split.next()?);
-				for range in ranges.split(',') {
-					let mut split = range.splitn(2, '-');
-					let (from, to) = (split.next().and_then(|f| f.parse::<usize>().ok())?, split.next().and_then(|f| f.parse::<usize>().ok())?);
-					emotes.push((id.to_owned(), from, to));
-				}
+		let mut split = emote.splitn(2, ':');
+		let (id, emote_ranges) = (split.next()?, split.next()?);
+		for range in emote_ranges.split(',') {
+			let mut split = range.splitn(2, '-');
+			let (from, to) = (split.next().and_then(|f| f.parse::<usize>().ok())?, split.next().and_then(|f| f.parse::<usize>().ok())?);
+			ranges.push((id.to_owned(), from, to));
+		}
+	}
+	ranges.sort_by_key(|a| a.1);
+
+	let mut segments = Vec::with_capacity(ranges.len());
+	if !ranges.is_empty() {
+		let mut i = 0;
+		for (id, start, end) in ranges {
+			if start > i {
+				segments.push(MessageSegment::Text {
+					text: get_utf8_slice(&msg, i, start)?.to_owned()
+				});
+			}
+			if end >= start {
+				segments.push(MessageSegment::Emote {
+					name: get_utf8_slice(&msg, start, end + 1)?.to_owned(),
+					id
+				});
+				i = end + 1;
 			}
-			emotes.sort_by_key(|a| a.1);
+		}
+		// `i` is a character index, not a byte index.
+		if let Some((rest, _)) = msg.char_indices().nth(i) {
+			segments.push(MessageSegment::Text { text: msg[rest..].to_owned() });
+		}
+	} else {
+		segments.push(MessageSegment::Text { text: msg });
+	}
+	Some(segments)
+}
 
-			let mut segments = Vec::with_capacity(emotes.len());
-			if !emotes.is_empty() {
-				let mut i = 0;
-				for (id, start, end) in emotes {
-					if start > i {
-						segments.push(MessageSegment::Text {
-							text: get_utf8_slice(&msg, i, start)?.to_owned()
-						});
-					}
-					if end >= start {
-						segments.push(MessageSegment::Emote {
-							name: get_utf8_slice(&msg, start, end + 1)?.to_owned(),
-							id
-						});
-						i = end + 1;
+/// Splits the text segments of a message into space-separated words, replacing each word for which `f` returns a
+/// segment (e.g. a cheer or third-party emote) with that segment.
+pub(crate) fn split_words(segments: Vec<MessageSegment>, f: impl Fn(&str) -> Option<MessageSegment>) -> Vec<MessageSegment> {
+	let mut out = Vec::with_capacity(segments.len());
+	for segment in segments {
+		let MessageSegment::Text { text } = segment else {
+			out.push(segment);
+			continue;
+		};
+
+		let mut buffer = String::new();
+		for (i, word) in text.split(' ').enumerate() {
+			if i != 0 {
+				buffer.push(' ');
+			}
+			match f(word) {
+				Some(segment) => {
+					if !buffer.is_empty() {
+						out.push(MessageSegment::Text { text: std::mem::take(&mut buffer) });
 					}
+					out.push(segment);
 				}
-				if i < msg.len() {
-					segments.push(MessageSegment::Text {
-						text: get_utf8_slice(&msg, i, msg.len())?.to_string()
-					});
-				}
-			} else {
-				segments.push(MessageSegment::Text { text: msg });
-			}
-
-			let user = User {
-				username,
-				display_name: user_display_name,
-				display_color: color,
-				role: match tags.remove("user-type").as_deref() {
-					Some("admin") => UserRole::TwitchAdmin,
-					Some("global_mod") => UserRole::GlobalModerator,
-					Some("staff") => UserRole::TwitchStaff,
-					_ => match tags.remove("mod").as_deref() {
-						Some("1") => UserRole::Moderator,
-						_ => match badges.remove("broadcaster").as_deref() {
-							Some(_) => UserRole::Broadcaster,
-							_ => UserRole::Normal
-						}
-					}
-				},
-				returning_chatter: matches!(tags.remove("returning-chatter").as_deref(), Some("1")),
-				sub_months: badge_info.remove("subscriber").and_then(|f| f.parse().ok()),
-				id: tags.remove("user-id").and_then(|f| f.parse().ok())?
+				None => buffer.push_str(word)
+			}
+		}
+		if !buffer.is_empty() {
+			out.push(MessageSegment::Text { text: buffer });
+		}
+	}
+	out
+}
+
+pub(crate) fn to_chat_event(message: irc::proto::Message, cheermotes: &Cheermotes) -> Option<ChatEvent> {
+	match message.command {
+		Command::PRIVMSG(target, msg) => {
+			let mut tags = collect_tags(message.tags)?;
+			let channel = Channel::from_target(&target, &mut tags);
+
+			let user = match message.prefix? {
+				irc::proto::Prefix::Nickname(n1, n2, _) => parse_user(n1, n2, &mut tags)?,
+				_ => return None
 			};
+			// Emote ranges are relative to the message without the `ACTION` wrapper.
+			let (msg, is_action) = strip_action(msg);
+			let segments = parse_segments(msg, &tags.remove("emotes")?)?;
 
 			let id = tags.remove("id").and_then(|f| f.parse().ok())?;
 			let sent_at = tags.remove("tmi-sent-ts").and_then(|f| f.parse::<i64>().ok())?;
 
 			if let Some(bits) = tags.remove("bits").and_then_nonempty(|f| f.parse().ok()) {
 				return Some(ChatEvent::SendBits {
+					channel,
 					id,
 					user,
 					bits,
 					sent_at_ms: sent_at,
-					segments
+					shared_chat: SharedChatSource::from_tags(&mut tags),
+					reply_to: ReplyInfo::from_tags(&mut tags),
+					first_message: matches!(tags.remove("first-msg").as_deref(), Some("1")),
+					is_action,
+					segments: cheermotes.split_segments(segments)
 				});
 			}
 
 			Some(ChatEvent::Message {
+				channel,
 				id,
 				user,
-				reply_to: tags.remove("reply-parent-msg-id").and_then(|f| f.parse().ok()),
+				reply_to: ReplyInfo::from_tags(&mut tags),
+				reward_id: tags.remove("custom-reward-id").and_then(|f| f.parse().ok()),
+				effect: MessageEffect::from_tags(&mut tags),
 				sent_at_ms: sent_at,
+				shared_chat: SharedChatSource::from_tags(&mut tags),
 				emote_only: matches!(tags.remove("emote-only").as_deref(), Some("1")),
 				first_message: matches!(tags.remove("first-msg").as_deref(), Some("1")),
+				is_action,
 				contents: segments
 			})
 		}
-		Command::Response(Response::RPL_NAMREPLY, names) => Some(ChatEvent::MemberChunk { names: names[3..].to_vec() }),
-		Command::Response(Response::RPL_ENDOFNAMES, _) => Some(ChatEvent::EndOfMembers),
+		Command::Raw(command, mut args) if command == "WHISPER" => {
+			let mut tags = collect_tags(message.tags)?;
+			let user = match message.prefix? {
+				irc::proto::Prefix::Nickname(n1, n2, _) => parse_user(n1, n2, &mut tags)?,
+				_ => return None
+			};
+			let contents = args.pop()?;
+			Some(ChatEvent::Whisper {
+				user,
+				recipient: args.pop()?,
+				thread_id: tags.remove("thread-id")?,
+				message_id: tags.remove("message-id")?,
+				contents: parse_segments(contents, tags.remove("emotes").as_deref().unwrap_or_default())?
+			})
+		}
+		Command::Raw(command, mut args) if command == "USERNOTICE" => {
+			let mut tags = collect_tags(message.tags)?;
+			let channel = Channel::from_target(args.first()?, &mut tags);
+
+			let username = tags.remove("login")?;
+			let user = parse_user(username.clone(), username, &mut tags)?;
+			let id = tags.remove("id").and_then(|f| f.parse().ok())?;
+			let sent_at_ms = tags.remove("tmi-sent-ts").and_then(|f| f.parse::<i64>().ok())?;
+			let system_message = tags.remove("system-msg").unwrap_or_default();
+			let message = match args.len() {
+				2 => Some(parse_segments(args.pop()?, tags.remove("emotes").as_deref().unwrap_or_default())?),
+				_ => None
+			};
+
+			let shared_chat = SharedChatSource::from_tags(&mut tags);
+			// Events shared from another channel in a shared chat session are wrapped in a `sharedchatnotice`, with the
+			// original event type in `source-msg-id`.
+			let msg_id = match tags.remove("msg-id")? {
+				msg_id if msg_id == "sharedchatnotice" => tags.remove("source-msg-id")?,
+				msg_id => msg_id
+			};
+			let mut param = |key: &str| tags.remove(&format!("msg-param-{key}"));
+			match msg_id.as_str() {
+				"sub" => Some(ChatEvent::Subscription {
+					channel,
+					id,
+					user,
+					sent_at_ms,
+					shared_chat,
+					tier: param("sub-plan").as_deref().and_then(SubTier::from_plan)?,
+					system_message,
+					message
+				}),
+				"resub" => Some(ChatEvent::Resubscription {
+					channel,
+					id,
+					user,
+					sent_at_ms,
+					shared_chat,
+					tier: param("sub-plan").as_deref().and_then(SubTier::from_plan)?,
+					cumulative_months: param("cumulative-months").and_then(|f| f.parse().ok())?,
+					streak_months: match param("should-share-streak").as_deref() {
+						Some("1") => param("streak-months").and_then(|f| f.parse().ok()),
+						_ => None
+					},
+					system_message,
+					message
+				}),
+				"subgift" => Some(ChatEvent::SubGift {
+					channel,
+					id,
+					user,
+					sent_at_ms,
+					shared_chat,
+					tier: param("sub-plan").as_deref().and_then(SubTier::from_plan)?,
+					recipient: GiftRecipient {
+						username: param("recipient-user-name")?,
+						display_name: param("recipient-display-name")?,
+						id: param("recipient-id").and_then(|f| f.parse().ok())?
+					},
+					recipient_months: param("months").and_then(|f| f.parse().ok())?,
+					gift_months: param("gift-months").and_then(|f| f.parse().ok()),
+					sender_total: param("sender-count").and_then(|f| f.parse().ok()),
+					system_message
+				}),
+				"submysterygift" => Some(ChatEvent::CommunityGift {
+					channel,
+					id,
+					user,
+					sent_at_ms,
+					shared_chat,
+					tier: param("sub-plan").as_deref().and_then(SubTier::from_plan)?,
+					count: param("mass-gift-count").and_then(|f| f.parse().ok())?,
+					sender_total: param("sender-count").and_then(|f| f.parse().ok()),
+					system_message
+				}),
+				"giftpaidupgrade" | "anongiftpaidupgrade" => Some(ChatEvent::GiftUpgrade {
+					channel,
+					id,
+					user,
+					sent_at_ms,
+					shared_chat,
+					gifter_username: param("sender-login"),
+					gifter_display_name: param("sender-name"),
+					system_message
+				}),
+				"primepaidupgrade" => Some(ChatEvent::PrimeUpgrade {
+					channel,
+					id,
+					user,
+					sent_at_ms,
+					shared_chat,
+					tier: param("sub-plan").as_deref().and_then(SubTier::from_plan)?,
+					system_message
+				}),
+				"raid" => Some(ChatEvent::Raid {
+					channel,
+					id,
+					user,
+					sent_at_ms,
+					shared_chat,
+					viewer_count: param("viewerCount").and_then(|f| f.parse().ok())?,
+					system_message
+				}),
+				"announcement" => Some(ChatEvent::Announcement {
+					channel,
+					id,
+					user,
+					sent_at_ms,
+					shared_chat,
+					color: param("color").as_deref().map(AnnouncementColor::from_param).unwrap_or_default(),
+					system_message,
+					contents: message.unwrap_or_default()
+				}),
+				"bitsbadgetier" => Some(ChatEvent::BitsBadgeTier {
+					channel,
+					id,
+					user,
+					sent_at_ms,
+					shared_chat,
+					threshold: param("threshold").and_then(|f| f.parse().ok())?,
+					system_message,
+					message
+				}),
+				_ => None
+			}
+		}
+		Command::Raw(command, mut args) if command == "CLEARCHAT" => {
+			let mut tags = collect_tags(message.tags)?;
+			let channel = Channel::from_target(args.first()?, &mut tags);
+			let sent_at_ms = tags.remove("tmi-sent-ts").and_then(|f| f.parse::<i64>().ok())?;
+			let Some(username) = args.get_mut(1).map(std::mem::take) else {
+				return Some(ChatEvent::ChatCleared { channel, sent_at_ms });
+			};
+
+			let user_id = tags.remove("target-user-id").and_then(|f| f.parse().ok())?;
+			match tags.remove("ban-duration").and_then(|f| f.parse().ok()) {
+				Some(duration_secs) => Some(ChatEvent::UserTimedOut {
+					channel,
+					username,
+					user_id,
+					duration_secs,
+					sent_at_ms
+				}),
+				None => Some(ChatEvent::UserBanned {
+					channel,
+					username,
+					user_id,
+					sent_at_ms
+				})
+			}
+		}
+		Command::Raw(command, mut args) if command == "CLEARMSG" => {
+			let mut tags = collect_tags(message.tags)?;
+			let channel = Channel::from_target(args.first()?, &mut tags);
+			Some(ChatEvent::MessageDeleted {
+				channel,
+				id: tags.remove("target-msg-id").and_then(|f| f.parse().ok())?,
+				username: tags.remove("login")?,
+				contents: args.get_mut(1).map(std::mem::take).unwrap_or_default(),
+				sent_at_ms: tags.remove("tmi-sent-ts").and_then(|f| f.parse::<i64>().ok())?
+			})
+		}
+		Command::Raw(command, args) if command == "USERSTATE" => {
+			let mut tags = collect_tags(message.tags)?;
+			let channel = Channel::from_target(args.first()?, &mut tags);
+			Some(ChatEvent::UserState {
+				channel,
+				message_id: tags.remove("id").and_then(|f| f.parse().ok()),
+				state: UserState::from_tags(&mut tags)
+			})
+		}
+		Command::Raw(command, _) if command == "GLOBALUSERSTATE" => {
+			let mut tags = collect_tags(message.tags)?;
+			Some(ChatEvent::GlobalUserState {
+				user_id: tags.remove("user-id").and_then(|f| f.parse().ok())?,
+				state: UserState::from_tags(&mut tags)
+			})
+		}
+		Command::Raw(command, args) if command == "ROOMSTATE" => {
+			let mut tags = collect_tags(message.tags)?;
+			let channel = Channel::from_target(args.first()?, &mut tags);
+			channel.room_id?;
+			let mut flag = |key: &str| tags.remove(key).map(|f| f == "1");
+			let update = RoomStateUpdate {
+				emote_only: flag("emote-only"),
+				subs_only: flag("subs-only"),
+				unique_chat: flag("r9k"),
+				followers_only: tags.remove("followers-only").and_then(|f| match f.parse::<i32>().ok()? {
+					..0 => Some(FollowersOnly::Disabled),
+					min_follow_minutes => Some(FollowersOnly::Enabled {
+						min_follow_minutes: min_follow_minutes as u32
+					})
+				}),
+				slow_mode_secs: tags.remove("slow").and_then(|f| f.parse().ok())
+			};
+			Some(ChatEvent::RoomState { channel, update })
+		}
+		// `:tmi.twitch.tv 353 <user> = #<channel> :<user> <user> ...`
+		Command::Response(Response::RPL_NAMREPLY, args) => Some(ChatEvent::MemberChunk {
+			channel: Channel::from_name(args.get(2)?),
+			names: args[3..].iter().flat_map(|names| names.split_whitespace()).map(str::to_owned).collect()
+		}),
+		Command::NOTICE(target, text) => {
+			let mut tags = collect_tags(message.tags).unwrap_or_default();
+			Some(ChatEvent::Notice {
+				channel: (target != "*").then(|| Channel::from_target(&target, &mut tags)),
+				kind: tags.remove("msg-id").map(|msg_id| NoticeKind::from_msg_id(&msg_id)),
+				message: text
+			})
+		}
+		Command::Response(Response::RPL_ENDOFNAMES, args) => Some(ChatEvent::EndOfMembers {
+			channel: Channel::from_name(args.get(1)?)
+		}),
+		Command::JOIN(ref channel, ..) => Some(ChatEvent::Join {
+			channel: Channel::from_name(channel),
+			username: message.source_nickname()?.to_owned()
+		}),
+		Command::PART(ref channel, _) => Some(ChatEvent::Part {
+			channel: Channel::from_name(channel),
+			username: message.source_nickname()?.to_owned()
+		}),
 		_ => None
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::{
+		AnnouncementColor, ChatEvent, Cheermotes, FollowersOnly, MessageEffect, MessageSegment, NoticeKind, RoomState, SendRejection, SubTier, UserRole,
+		to_chat_event
+	};
+
+	fn parse(line: &str) -> Option<ChatEvent> {
+		to_chat_event(line.parse().unwrap(), &Cheermotes::default())
+	}
+
+	fn text(segments: &[MessageSegment]) -> String {
+		segments.iter().map(ToString::to_string).collect()
+	}
+
+	/// Parses `$line` and evaluates `$body` with the event destructured by `$pattern`, panicking if it doesn't match.
+	macro_rules! event {
+		($line:expr, $pattern:pat => $body:block) => {
+			match parse($line) {
+				Some($pattern) => $body,
+				event => panic!("expected {}, got {event:?}", stringify!($pattern))
+			}
+		};
+	}
+
+	#[test]
+	fn subscriptions() {
+		event!(
+			"@badge-info=subscriber/12;badges=subscriber/12;color=#008000;display-name=YukiFan4;emotes=;id=5f3d9a72-3c46-4fc1-9b2b-8e5c0b6c1f5e;login=yukifan4;mod=0;msg-id=resub;msg-param-cumulative-months=12;msg-param-should-share-streak=1;msg-param-streak-months=3;msg-param-sub-plan=1000;room-id=1;subscriber=1;system-msg=YukiFan4\\ssubscribed\\sat\\sTier\\s1.;tmi-sent-ts=1700000000000;user-id=2;user-type= :tmi.twitch.tv USERNOTICE #miyukiwei :one year!",
+			ChatEvent::Resubscription {
+				channel,
+				user,
+				tier,
+				cumulative_months,
+				streak_months,
+				system_message,
+				message,
+				..
+			} => {
+				assert_eq!(channel.name, "miyukiwei");
+				assert_eq!(channel.room_id, Some(1));
+				assert_eq!(user.display_name, "YukiFan4");
+				assert_eq!(user.sub_months.map(|months| months.get()), Some(12));
+				assert_eq!(tier, SubTier::Tier1);
+				assert_eq!(cumulative_months.get(), 12);
+				assert_eq!(streak_months.map(|months| months.get()), Some(3));
+				assert_eq!(system_message, "YukiFan4 subscribed at Tier 1.");
+				assert_eq!(text(&message.unwrap()), "one year!");
+			}
+		);
+		event!(
+			"@badges=;color=;display-name=YukiFan4;emotes=;id=8f6b0c8e-5a3f-4d52-b4c1-1c1f5b7f6a0d;login=yukifan4;mod=0;msg-id=subgift;msg-param-gift-months=1;msg-param-months=5;msg-param-recipient-display-name=Miyu;msg-param-recipient-id=3;msg-param-recipient-user-name=miyu;msg-param-sender-count=0;msg-param-sub-plan=2000;room-id=1;subscriber=0;system-msg=;tmi-sent-ts=1700000000000;user-id=2;user-type= :tmi.twitch.tv USERNOTICE #miyukiwei",
+			ChatEvent::SubGift { tier, recipient, recipient_months, gift_months, sender_total, .. } => {
+				assert_eq!(tier, SubTier::Tier2);
+				assert_eq!((recipient.username.as_str(), recipient.id), ("miyu", 3));
+				assert_eq!(recipient_months.get(), 5);
+				assert_eq!(gift_months.map(|months| months.get()), Some(1));
+				assert_eq!(sender_total, Some(0));
+			}
+		);
+		event!(
+			"@badges=;color=;display-name=YukiFan4;emotes=;id=8f6b0c8e-5a3f-4d52-b4c1-1c1f5b7f6a0d;login=yukifan4;mod=0;msg-id=submysterygift;msg-param-mass-gift-count=5;msg-param-sub-plan=Prime;room-id=1;subscriber=0;system-msg=;tmi-sent-ts=1700000000000;user-id=2;user-type= :tmi.twitch.tv USERNOTICE #miyukiwei",
+			ChatEvent::CommunityGift { tier, count, .. } => {
+				assert_eq!(tier, SubTier::Prime);
+				assert_eq!(count.get(), 5);
+			}
+		);
+	}
+
+	#[test]
+	fn raids_and_announcements() {
+		event!(
+			"@badges=;color=;display-name=YukiFan4;emotes=;id=8f6b0c8e-5a3f-4d52-b4c1-1c1f5b7f6a0d;login=yukifan4;mod=0;msg-id=raid;msg-param-displayName=YukiFan4;msg-param-login=yukifan4;msg-param-viewerCount=42;room-id=1;subscriber=0;system-msg=;tmi-sent-ts=1700000000000;user-id=2;user-type= :tmi.twitch.tv USERNOTICE #miyukiwei",
+			ChatEvent::Raid { user, viewer_count, .. } => {
+				assert_eq!(user.username, "yukifan4");
+				assert_eq!(viewer_count, 42);
+			}
+		);
+		event!(
+			"@badges=moderator/1;color=;display-name=YukiFan4;emotes=;id=8f6b0c8e-5a3f-4d52-b4c1-1c1f5b7f6a0d;login=yukifan4;mod=1;msg-id=announcement;msg-param-color=PURPLE;room-id=1;subscriber=0;system-msg=;tmi-sent-ts=1700000000000;user-id=2;user-type= :tmi.twitch.tv USERNOTICE #miyukiwei :stream starting soon",
+			ChatEvent::Announcement { user, color, contents, .. } => {
+				assert_eq!(user.role, UserRole::Moderator);
+				assert_eq!(color, AnnouncementColor::Purple);
+				assert_eq!(text(&contents), "stream starting soon");
+			}
+		);
+
+		// Unknown `msg-id`s are ignored.
+		assert!(
+			parse(
+				"@badges=;color=;display-name=YukiFan4;emotes=;id=8f6b0c8e-5a3f-4d52-b4c1-1c1f5b7f6a0d;login=yukifan4;mod=0;msg-id=unknown;room-id=1;subscriber=0;system-msg=;tmi-sent-ts=1700000000000;user-id=2;user-type= :tmi.twitch.tv USERNOTICE #miyukiwei"
+			)
+			.is_none()
+		);
+	}
+
+	#[test]
+	fn moderation() {
+		event!(
+			"@ban-duration=600;room-id=1;target-user-id=2;tmi-sent-ts=1700000000000 :tmi.twitch.tv CLEARCHAT #miyukiwei :yukifan4",
+			ChatEvent::UserTimedOut { username, user_id, duration_secs, .. } => {
+				assert_eq!((username.as_str(), user_id, duration_secs), ("yukifan4", 2, 600));
+			}
+		);
+		event!(
+			"@room-id=1;target-user-id=2;tmi-sent-ts=1700000000000 :tmi.twitch.tv CLEARCHAT #miyukiwei :yukifan4",
+			ChatEvent::UserBanned { username, user_id, .. } => {
+				assert_eq!((username.as_str(), user_id), ("yukifan4", 2));
+			}
+		);
+		event!(
+			"@room-id=1;tmi-sent-ts=1700000000000 :tmi.twitch.tv CLEARCHAT #miyukiwei",
+			ChatEvent::ChatCleared { sent_at_ms, .. } => {
+				assert_eq!(sent_at_ms, 1700000000000);
+			}
+		);
+		event!(
+			"@login=yukifan4;room-id=;target-msg-id=b34ccfc7-4977-403a-8a94-33c6bac34fb8;tmi-sent-ts=1700000000000 :tmi.twitch.tv CLEARMSG #miyukiwei :oops",
+			ChatEvent::MessageDeleted { id, username, contents, .. } => {
+				assert_eq!(id.to_string(), "b34ccfc7-4977-403a-8a94-33c6bac34fb8");
+				assert_eq!((username.as_str(), contents.as_str()), ("yukifan4", "oops"));
+			}
+		);
+	}
+
+	#[test]
+	fn room_state() {
+		let mut state = RoomState::default();
+		event!(
+			"@emote-only=0;followers-only=10;r9k=0;room-id=1;slow=30;subs-only=1 :tmi.twitch.tv ROOMSTATE #miyukiwei",
+			ChatEvent::RoomState { channel, update } => {
+				assert_eq!(channel.name, "miyukiwei");
+				assert_eq!(update.emote_only, Some(false));
+				assert_eq!(update.followers_only, Some(FollowersOnly::Enabled { min_follow_minutes: 10 }));
+				assert_eq!(update.unique_chat, Some(false));
+				assert_eq!(update.slow_mode_secs, Some(30));
+				assert_eq!(update.subs_only, Some(true));
+				state.apply(channel.room_id, &update);
+			}
+		);
+
+		// Later updates only carry the setting that changed.
+		event!(
+			"@followers-only=-1;room-id=1 :tmi.twitch.tv ROOMSTATE #miyukiwei",
+			ChatEvent::RoomState { channel, update } => {
+				assert_eq!(update.followers_only, Some(FollowersOnly::Disabled));
+				assert_eq!(update.slow_mode_secs, None);
+				state.apply(channel.room_id, &update);
+			}
+		);
+		assert_eq!(state.room_id, Some(1));
+		assert_eq!(state.followers_only, FollowersOnly::Disabled);
+		assert_eq!(state.slow_mode_secs, 30);
+		assert!(state.subs_only);
+	}
+
+	#[test]
+	fn badges() {
+		event!(
+			"@badge-info=subscriber/14,predictions/blue\\sside;badges=vip/1,founder/0,predictions/blue-1;color=;display-name=yukifan4;emotes=;id=b34ccfc7-4977-403a-8a94-33c6bac34fb8;room-id=1;tmi-sent-ts=1700000000000;user-id=2 :yukifan4!yukifan4@yukifan4.tmi.twitch.tv PRIVMSG #miyukiwei :hi",
+			ChatEvent::Message { user, .. } => {
+				assert_eq!(user.badges.len(), 3);
+				assert!(user.flags.vip && user.flags.founder && user.flags.subscriber && !user.flags.artist);
+				assert_eq!(user.badge("founder").map(|badge| badge.version.as_str()), Some("0"));
+				assert_eq!(user.badge("predictions").and_then(|badge| badge.info.as_deref()), Some("blue side"));
+				assert_eq!(user.role, UserRole::Normal);
+			}
+		);
+	}
+
+	#[test]
+	fn replies() {
+		event!(
+			"@badges=;color=;display-name=yukifan4;emotes=;id=b34ccfc7-4977-403a-8a94-33c6bac34fb8;reply-parent-display-name=Miyu;reply-parent-msg-body=hello\\sthere;reply-parent-msg-id=6d2a8c4e-0f57-4d8b-9f0a-3b6f0e9b1c2d;reply-parent-user-id=3;reply-parent-user-login=miyu;reply-thread-parent-msg-id=1c9f2e3d-7a6b-4c5d-8e9f-0a1b2c3d4e5f;reply-thread-parent-user-login=miyukiwei;room-id=1;tmi-sent-ts=1700000000000;user-id=2 :yukifan4!yukifan4@yukifan4.tmi.twitch.tv PRIVMSG #miyukiwei :@miyu hi",
+			ChatEvent::Message { reply_to, contents, .. } => {
+				let reply_to = reply_to.unwrap();
+				assert_eq!(reply_to.parent_id.to_string(), "6d2a8c4e-0f57-4d8b-9f0a-3b6f0e9b1c2d");
+				assert_eq!(reply_to.parent_user_id, Some(3));
+				assert_eq!((reply_to.parent_username.as_str(), reply_to.parent_display_name.as_str()), ("miyu", "Miyu"));
+				assert_eq!(reply_to.parent_message, "hello there");
+				assert_eq!(reply_to.thread_id.to_string(), "1c9f2e3d-7a6b-4c5d-8e9f-0a1b2c3d4e5f");
+				assert_eq!(reply_to.thread_username.as_deref(), Some("miyukiwei"));
+				assert_eq!(text(&contents), "@miyu hi");
+			}
+		);
+
+		// The thread tags are omitted when replying to a message that isn't itself a reply.
+		event!(
+			"@badges=;color=;display-name=yukifan4;emotes=;id=b34ccfc7-4977-403a-8a94-33c6bac34fb8;reply-parent-display-name=Miyu;reply-parent-msg-body=hi;reply-parent-msg-id=6d2a8c4e-0f57-4d8b-9f0a-3b6f0e9b1c2d;reply-parent-user-id=3;reply-parent-user-login=miyu;room-id=1;tmi-sent-ts=1700000000000;user-id=2 :yukifan4!yukifan4@yukifan4.tmi.twitch.tv PRIVMSG #miyukiwei :@miyu hi",
+			ChatEvent::Message { reply_to, .. } => {
+				let reply_to = reply_to.unwrap();
+				assert_eq!(reply_to.thread_id, reply_to.parent_id);
+				assert_eq!(reply_to.thread_username, None);
+			}
+		);
+	}
+
+	#[test]
+	fn cheers() {
+		const CHEER: &str = "@badges=bits/100;bits=150;color=;display-name=yukifan4;emotes=;id=b34ccfc7-4977-403a-8a94-33c6bac34fb8;room-id=1;tmi-sent-ts=1700000000000;user-id=2 :yukifan4!yukifan4@yukifan4.tmi.twitch.tv PRIVMSG #miyukiwei :Cheer100 great stream cheer50 yuki25";
+		event!(CHEER, ChatEvent::SendBits { bits, segments, .. } => {
+			assert_eq!(bits.get(), 150);
+			assert!(matches!(&segments[0], MessageSegment::Cheer { prefix, amount } if prefix == "Cheer" && amount.get() == 100));
+			assert!(matches!(&segments[1], MessageSegment::Text { text } if text == " great stream "));
+			assert!(matches!(&segments[2], MessageSegment::Cheer { prefix, amount } if prefix == "cheer" && amount.get() == 50));
+			assert!(matches!(&segments[3], MessageSegment::Text { text } if text == " yuki25"));
+		});
+
+		// Channel-specific cheermotes are only recognized when passed in.
+		let Some(ChatEvent::SendBits { segments, .. }) = to_chat_event(CHEER.parse().unwrap(), &Cheermotes::new(["yuki"])) else {
+			panic!("expected a cheer")
+		};
+		assert!(matches!(&segments[0], MessageSegment::Text { text } if text == "Cheer100 great stream cheer50 "));
+		assert!(matches!(&segments[1], MessageSegment::Cheer { prefix, amount } if prefix == "yuki" && amount.get() == 25));
+	}
+
+	#[test]
+	fn effects() {
+		event!(
+			"@badges=;color=;custom-reward-id=0f1e2d3c-4b5a-6978-8796-a5b4c3d2e1f0;display-name=yukifan4;emotes=;id=b34ccfc7-4977-403a-8a94-33c6bac34fb8;room-id=1;tmi-sent-ts=1700000000000;user-id=2 :yukifan4!yukifan4@yukifan4.tmi.twitch.tv PRIVMSG #miyukiwei :hi",
+			ChatEvent::Message { reward_id, effect, .. } => {
+				assert_eq!(reward_id.unwrap().to_string(), "0f1e2d3c-4b5a-6978-8796-a5b4c3d2e1f0");
+				assert_eq!(effect, None);
+			}
+		);
+		event!(
+			"@animation-id=simmer;badges=;color=;display-name=yukifan4;emotes=;id=b34ccfc7-4977-403a-8a94-33c6bac34fb8;msg-id=animated-message;room-id=1;tmi-sent-ts=1700000000000;user-id=2 :yukifan4!yukifan4@yukifan4.tmi.twitch.tv PRIVMSG #miyukiwei :hi",
+			ChatEvent::Message { effect, .. } => {
+				assert_eq!(effect, Some(MessageEffect::Animated { animation_id: "simmer".to_owned() }));
+			}
+		);
+		event!(
+			"@badges=;color=;display-name=yukifan4;emotes=;id=b34ccfc7-4977-403a-8a94-33c6bac34fb8;msg-id=highlighted-message;room-id=1;tmi-sent-ts=1700000000000;user-id=2 :yukifan4!yukifan4@yukifan4.tmi.twitch.tv PRIVMSG #miyukiwei :hi",
+			ChatEvent::Message { effect, .. } => {
+				assert_eq!(effect, Some(MessageEffect::Highlighted));
+			}
+		);
+	}
+
+	#[test]
+	fn actions() {
+		event!(
+			"@badges=;color=;display-name=yukifan4;emotes=25:6-10;id=b34ccfc7-4977-403a-8a94-33c6bac34fb8;room-id=1;tmi-sent-ts=1700000000000;user-id=2 :yukifan4!yukifan4@yukifan4.tmi.twitch.tv PRIVMSG #miyukiwei :\u{1}ACTION waves Kappa ✨\u{1}",
+			ChatEvent::Message { is_action, contents, .. } => {
+				assert!(is_action);
+				// Emote ranges are relative to the text without the `ACTION` wrapper.
+				assert!(matches!(&contents[0], MessageSegment::Text { text } if text == "waves "));
+				assert!(matches!(&contents[1], MessageSegment::Emote { name, id } if name == "Kappa" && id == "25"));
+				assert!(matches!(&contents[2], MessageSegment::Text { text } if text == " ✨"));
+			}
+		);
+		event!(
+			"@badges=;color=;display-name=yukifan4;emotes=25:6-10,12-16;id=b34ccfc7-4977-403a-8a94-33c6bac34fb8;room-id=1;tmi-sent-ts=1700000000000;user-id=2 :yukifan4!yukifan4@yukifan4.tmi.twitch.tv PRIVMSG #miyukiwei :héllo Kappa Kappa",
+			ChatEvent::Message { is_action, contents, .. } => {
+				assert!(!is_action);
+				assert_eq!(contents.len(), 4);
+				assert!(matches!(&contents[3], MessageSegment::Emote { name, .. } if name == "Kappa"));
+			}
+		);
+	}
+
+	#[test]
+	fn notices() {
+		event!(
+			"@msg-id=msg_duplicate :tmi.twitch.tv NOTICE #miyukiwei :Your message is identical to the one you sent less than 30 seconds ago.",
+			ChatEvent::Notice { channel, kind, message } => {
+				assert_eq!(channel.unwrap().name, "miyukiwei");
+				assert_eq!(kind, Some(NoticeKind::MessageRejected(SendRejection::Duplicate)));
+				assert!(message.starts_with("Your message is identical"));
+			}
+		);
+		event!(
+			"@msg-id=unrecognized_cmd :tmi.twitch.tv NOTICE #miyukiwei :Unrecognized command: /foo",
+			ChatEvent::Notice { kind, .. } => {
+				assert_eq!(kind, Some(NoticeKind::UnrecognizedCommand));
+			}
+		);
+		event!(
+			":tmi.twitch.tv NOTICE * :Login authentication failed",
+			ChatEvent::Notice { channel, kind, .. } => {
+				assert_eq!(channel, None);
+				assert_eq!(kind, None);
+			}
+		);
+	}
+
+	#[test]
+	fn shared_chat() {
+		event!(
+			"@badges=;color=;display-name=yukifan4;emotes=;id=b34ccfc7-4977-403a-8a94-33c6bac34fb8;room-id=1;source-badge-info=subscriber/3;source-badges=subscriber/3;source-id=6d2a8c4e-0f57-4d8b-9f0a-3b6f0e9b1c2d;source-room-id=4;tmi-sent-ts=1700000000000;user-id=2 :yukifan4!yukifan4@yukifan4.tmi.twitch.tv PRIVMSG #miyukiwei :hi",
+			ChatEvent::Message { shared_chat, user, .. } => {
+				let shared_chat = shared_chat.unwrap();
+				assert_eq!(shared_chat.room_id, 4);
+				assert_eq!(shared_chat.message_id.to_string(), "6d2a8c4e-0f57-4d8b-9f0a-3b6f0e9b1c2d");
+				assert_eq!(shared_chat.badges[0].info.as_deref(), Some("3"));
+				assert!(user.badges.is_empty());
+			}
+		);
+
+		// Events shared from another channel are wrapped in a `sharedchatnotice`.
+		event!(
+			"@badges=;color=;display-name=YukiFan4;emotes=;id=8f6b0c8e-5a3f-4d52-b4c1-1c1f5b7f6a0d;login=yukifan4;mod=0;msg-id=sharedchatnotice;msg-param-viewerCount=7;room-id=1;source-id=6d2a8c4e-0f57-4d8b-9f0a-3b6f0e9b1c2d;source-msg-id=raid;source-room-id=4;subscriber=0;system-msg=;tmi-sent-ts=1700000000000;user-id=2;user-type= :tmi.twitch.tv USERNOTICE #miyukiwei",
+			ChatEvent::Raid { shared_chat, viewer_count, .. } => {
+				assert_eq!(shared_chat.map(|source| source.room_id), Some(4));
+				assert_eq!(viewer_count, 7);
+			}
+		);
+
+		// Messages sent in the channel itself have no source.
+		event!(
+			"@badges=;color=;display-name=yukifan4;emotes=;id=b34ccfc7-4977-403a-8a94-33c6bac34fb8;room-id=1;tmi-sent-ts=1700000000000;user-id=2 :yukifan4!yukifan4@yukifan4.tmi.twitch.tv PRIVMSG #miyukiwei :hi",
+			ChatEvent::Message { shared_chat, .. } => {
+				assert!(shared_chat.is_none());
+			}
+		);
+	}
+
+	#[test]
+	fn user_state() {
+		event!(
+			"@badge-info=;badges=moderator/1;color=#FF4500;display-name=YukiFan4;emote-sets=0,33,50;mod=1;subscriber=0;user-type=mod :tmi.twitch.tv USERSTATE #miyukiwei",
+			ChatEvent::UserState { channel, state, message_id } => {
+				assert_eq!(channel.name, "miyukiwei");
+				assert_eq!(message_id, None);
+				assert_eq!(state.display_color, Some(0xFF4500));
+				assert!(state.is_moderator() && !state.is_vip());
+				assert_eq!(state.emote_sets, ["0", "33", "50"]);
+			}
+		);
+
+		// Sent in response to a message.
+		event!(
+			"@badge-info=;badges=;color=;display-name=YukiFan4;emote-sets=0;id=b34ccfc7-4977-403a-8a94-33c6bac34fb8;mod=0;subscriber=0;user-type= :tmi.twitch.tv USERSTATE #miyukiwei",
+			ChatEvent::UserState { state, message_id, .. } => {
+				assert_eq!(message_id.map(|id| id.to_string()).as_deref(), Some("b34ccfc7-4977-403a-8a94-33c6bac34fb8"));
+				assert!(!state.is_moderator());
+			}
+		);
+
+		event!(
+			"@badge-info=;badges=;color=;display-name=YukiFan4;emote-sets=0;user-id=2;user-type= :tmi.twitch.tv GLOBALUSERSTATE",
+			ChatEvent::GlobalUserState { user_id, state } => {
+				assert_eq!(user_id, 2);
+				assert_eq!(state.role, UserRole::Normal);
+			}
+		);
+	}
+
+	#[test]
+	fn membership() {
+		event!(":yukifan4!yukifan4@yukifan4.tmi.twitch.tv JOIN #miyukiwei", ChatEvent::Join { channel, username } => {
+			assert_eq!((channel.name.as_str(), username.as_str()), ("miyukiwei", "yukifan4"));
+		});
+		event!(":yukifan4!yukifan4@yukifan4.tmi.twitch.tv PART #miyukiwei", ChatEvent::Part { channel, username } => {
+			assert_eq!((channel.name.as_str(), username.as_str()), ("miyukiwei", "yukifan4"));
+		});
+		event!(":yukifan4.tmi.twitch.tv 353 yukifan4 = #miyukiwei :yukifan4 miyu miyukiwei", ChatEvent::MemberChunk { channel, names } => {
+			assert_eq!(channel.name, "miyukiwei");
+			assert_eq!(names, ["yukifan4", "miyu", "miyukiwei"]);
+		});
+	}
+
+	#[test]
+	fn whispers() {
+		event!(
+			"@badges=;color=;display-name=Miyu;emotes=25:4-8;message-id=7;thread-id=2_3;turbo=0;user-id=3;user-type= :miyu!miyu@miyu.tmi.twitch.tv WHISPER yukifan4 :hey Kappa",
+			ChatEvent::Whisper { user, recipient, thread_id, message_id, contents } => {
+				assert_eq!((user.username.as_str(), user.id), ("miyu", 3));
+				assert_eq!(recipient, "yukifan4");
+				assert_eq!((thread_id.as_str(), message_id.as_str()), ("2_3", "7"));
+				assert!(matches!(&contents[1], MessageSegment::Emote { name, .. } if name == "Kappa"));
+			}
+		);
+	}
+
+	#[test]
+	fn malformed_colors() {
+		for color in ["é", "FF0000", "#", "#zzzzzz"] {
+			event!(
+				&format!(
+					"@badges=;color={color};display-name=yukifan4;emotes=;id=b34ccfc7-4977-403a-8a94-33c6bac34fb8;room-id=1;tmi-sent-ts=1700000000000;user-id=2 :yukifan4!yukifan4@yukifan4.tmi.twitch.tv PRIVMSG #miyukiwei :hi"
+				),
+				ChatEvent::Message { user, .. } => {
+					assert_eq!(user.display_color, None);
+				}
+			);
+			event!(
+				&format!("@badges=;color={color};display-name=yukifan4;emote-sets=0;mod=0;user-type= :tmi.twitch.tv USERSTATE #miyukiwei"),
+				ChatEvent::UserState { state, .. } => {
+					assert_eq!(state.display_color, None);
+				}
+			);
+		}
+	}
+}
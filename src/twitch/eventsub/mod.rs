@@ -0,0 +1,538 @@
+// Copyright 2026 pyke.io
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Receive events via [EventSub](https://dev.twitch.tv/docs/eventsub/) over WebSocket.
+//!
+//! Many events, like channel point redemptions, polls & predictions, are not available over IRC and can only be
+//! received via EventSub. Unlike [`Chat`](super::Chat), EventSub requires a user access token, and subscriptions are
+//! created through the [Helix API](super::helix), so an [`EventSub`] connection must be given a [`helix::Client`].
+//!
+//! ```no_run
+//! # use brainrot::twitch::{eventsub::{EventSub, EventSubError, Subscription}, helix::{self, HelixError}, RequestExecutor};
+//! # use futures_util::StreamExt;
+//! # async fn run<E: RequestExecutor>(executor: E) -> Result<(), EventSubError<E>> {
+//! let client = helix::Client::new(executor, "<client ID>", "<user access token>");
+//! let mut events = EventSub::connect(client.map_err(HelixError::BadRequest)?).await?;
+//! events.subscribe(&Subscription::poll_begin("141981764")).await?;
+//! let event = events.next().await.transpose()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+	collections::BTreeMap,
+	error::Error as StdError,
+	fmt,
+	pin::Pin,
+	sync::{Arc, Mutex, PoisonError},
+	task::{self, Context},
+	time::Duration
+};
+
+use async_stream_lite::try_async_stream;
+use futures_util::{
+	Stream, StreamExt,
+	future::{BoxFuture, Either, select},
+	pin_mut,
+	stream::BoxStream
+};
+use http::Method;
+use pin_project_lite::pin_project;
+use simd_json::OwnedValue;
+use tokio_tungstenite::{
+	MaybeTlsStream, WebSocketStream,
+	tungstenite::{self, Message as WsMessage}
+};
+
+use super::helix::{self, DataResponse, HelixError};
+use crate::executor::RequestExecutor;
+
+mod types;
+pub use self::types::{
+	ChannelPointsRedemption, ChannelPointsVoting, Follow, HypeTrain, HypeTrainContribution, Poll, PollChoice, Prediction, PredictionOutcome, Reward,
+	SharedChatParticipant, SharedChatSession, SubscriptionInfo
+};
+use self::types::{CreateSubscriptionRequest, NotificationPayload, RevocationPayload, Session, SessionPayload, WebSocketMessage, WebSocketTransport};
+
+const EVENTSUB_WEBSOCKET_URL: &str = "wss://eventsub.wss.twitch.tv/ws";
+/// How long to wait for the `session_welcome` message after connecting.
+const WELCOME_TIMEOUT: Duration = Duration::from_secs(10);
+/// Extra leeway given on top of the session's keepalive timeout before the connection is considered dead.
+const KEEPALIVE_GRACE: Duration = Duration::from_secs(5);
+
+type Socket = WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>;
+
+/// An EventSub subscription to create via [`EventSub::subscribe`].
+///
+/// The [`Subscription`] type has constructors for the subscription types this module parses into [`Notification`]s;
+/// other types can be subscribed to with [`Subscription::new`] and will be received as [`Notification::Other`]. See the
+/// [subscription type reference](https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types/) for the conditions
+/// each type requires.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Subscription {
+	pub kind: String,
+	pub version: String,
+	pub condition: BTreeMap<String, String>
+}
+
+impl Subscription {
+	pub fn new(kind: impl Into<String>, version: impl Into<String>) -> Self {
+		Self {
+			kind: kind.into(),
+			version: version.into(),
+			condition: BTreeMap::new()
+		}
+	}
+
+	pub fn with_condition(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+		self.condition.insert(key.into(), value.into());
+		self
+	}
+
+	fn broadcaster(kind: &str, version: &str, broadcaster_user_id: impl Into<String>) -> Self {
+		Self::new(kind, version).with_condition("broadcaster_user_id", broadcaster_user_id)
+	}
+
+	/// Subscribes to all channel point redemptions in the channel. Requires the `channel:read:redemptions` scope.
+	pub fn channel_points_redemption(broadcaster_user_id: impl Into<String>) -> Self {
+		Self::broadcaster("channel.channel_points_custom_reward_redemption.add", "1", broadcaster_user_id)
+	}
+
+	/// Requires the `channel:read:polls` scope.
+	pub fn poll_begin(broadcaster_user_id: impl Into<String>) -> Self {
+		Self::broadcaster("channel.poll.begin", "1", broadcaster_user_id)
+	}
+
+	/// Requires the `channel:read:polls` scope.
+	pub fn poll_progress(broadcaster_user_id: impl Into<String>) -> Self {
+		Self::broadcaster("channel.poll.progress", "1", broadcaster_user_id)
+	}
+
+	/// Requires the `channel:read:polls` scope.
+	pub fn poll_end(broadcaster_user_id: impl Into<String>) -> Self {
+		Self::broadcaster("channel.poll.end", "1", broadcaster_user_id)
+	}
+
+	/// Requires the `channel:read:predictions` scope.
+	pub fn prediction_begin(broadcaster_user_id: impl Into<String>) -> Self {
+		Self::broadcaster("channel.prediction.begin", "1", broadcaster_user_id)
+	}
+
+	/// Requires the `channel:read:predictions` scope.
+	pub fn prediction_progress(broadcaster_user_id: impl Into<String>) -> Self {
+		Self::broadcaster("channel.prediction.progress", "1", broadcaster_user_id)
+	}
+
+	/// Requires the `channel:read:predictions` scope.
+	pub fn prediction_lock(broadcaster_user_id: impl Into<String>) -> Self {
+		Self::broadcaster("channel.prediction.lock", "1", broadcaster_user_id)
+	}
+
+	/// Requires the `channel:read:predictions` scope.
+	pub fn prediction_end(broadcaster_user_id: impl Into<String>) -> Self {
+		Self::broadcaster("channel.prediction.end", "1", broadcaster_user_id)
+	}
+
+	/// Requires the `channel:read:hype_train` scope.
+	pub fn hype_train_begin(broadcaster_user_id: impl Into<String>) -> Self {
+		Self::broadcaster("channel.hype_train.begin", "2", broadcaster_user_id)
+	}
+
+	/// Requires the `channel:read:hype_train` scope.
+	pub fn hype_train_progress(broadcaster_user_id: impl Into<String>) -> Self {
+		Self::broadcaster("channel.hype_train.progress", "2", broadcaster_user_id)
+	}
+
+	/// Requires the `channel:read:hype_train` scope.
+	pub fn hype_train_end(broadcaster_user_id: impl Into<String>) -> Self {
+		Self::broadcaster("channel.hype_train.end", "2", broadcaster_user_id)
+	}
+
+	/// Requires the `moderator:read:followers` scope, and the token's user must be a moderator in the channel.
+	pub fn follow(broadcaster_user_id: impl Into<String>, moderator_user_id: impl Into<String>) -> Self {
+		Self::broadcaster("channel.follow", "2", broadcaster_user_id).with_condition("moderator_user_id", moderator_user_id)
+	}
+
+	pub fn shared_chat_begin(broadcaster_user_id: impl Into<String>) -> Self {
+		Self::broadcaster("channel.shared_chat.begin", "1", broadcaster_user_id)
+	}
+
+	pub fn shared_chat_update(broadcaster_user_id: impl Into<String>) -> Self {
+		Self::broadcaster("channel.shared_chat.update", "1", broadcaster_user_id)
+	}
+
+	pub fn shared_chat_end(broadcaster_user_id: impl Into<String>) -> Self {
+		Self::broadcaster("channel.shared_chat.end", "1", broadcaster_user_id)
+	}
+}
+
+/// The contents of an EventSub notification.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Notification {
+	ChannelPointsRedemption(ChannelPointsRedemption),
+	PollBegin(Poll),
+	PollProgress(Poll),
+	PollEnd(Poll),
+	PredictionBegin(Prediction),
+	PredictionProgress(Prediction),
+	PredictionLock(Prediction),
+	PredictionEnd(Prediction),
+	HypeTrainBegin(HypeTrain),
+	HypeTrainProgress(HypeTrain),
+	HypeTrainEnd(HypeTrain),
+	Follow(Follow),
+	SharedChatBegin(SharedChatSession),
+	SharedChatUpdate(SharedChatSession),
+	SharedChatEnd(SharedChatSession),
+	/// A notification for a subscription type (or version) not parsed by this module, or one whose event couldn't be
+	/// parsed (e.g. because Twitch changed its fields).
+	Other(OwnedValue)
+}
+
+impl Notification {
+	/// Parses a notification's event, falling back to [`Notification::Other`] if it doesn't have the expected shape.
+	fn parse(kind: &str, version: &str, event: OwnedValue) -> Self {
+		use simd_json::serde::from_refowned_value as from;
+		let parsed = match (kind, version) {
+			("channel.channel_points_custom_reward_redemption.add", "1") => from(&event).map(Self::ChannelPointsRedemption),
+			("channel.poll.begin", "1") => from(&event).map(Self::PollBegin),
+			("channel.poll.progress", "1") => from(&event).map(Self::PollProgress),
+			("channel.poll.end", "1") => from(&event).map(Self::PollEnd),
+			("channel.prediction.begin", "1") => from(&event).map(Self::PredictionBegin),
+			("channel.prediction.progress", "1") => from(&event).map(Self::PredictionProgress),
+			("channel.prediction.lock", "1") => from(&event).map(Self::PredictionLock),
+			("channel.prediction.end", "1") => from(&event).map(Self::PredictionEnd),
+			("channel.hype_train.begin", "2") => from(&event).map(Self::HypeTrainBegin),
+			("channel.hype_train.progress", "2") => from(&event).map(Self::HypeTrainProgress),
+			("channel.hype_train.end", "2") => from(&event).map(Self::HypeTrainEnd),
+			("channel.follow", "2") => from(&event).map(Self::Follow),
+			("channel.shared_chat.begin", "1") => from(&event).map(Self::SharedChatBegin),
+			("channel.shared_chat.update", "1") => from(&event).map(Self::SharedChatUpdate),
+			("channel.shared_chat.end", "1") => from(&event).map(Self::SharedChatEnd),
+			_ => return Self::Other(event)
+		};
+		parsed.unwrap_or_else(|e| {
+			tracing::warn!("failed to parse {kind} v{version} notification: {e}");
+			Self::Other(event)
+		})
+	}
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Event {
+	Notification {
+		/// The ID of the message. Twitch may deliver the same notification more than once; duplicates share an ID.
+		message_id: String,
+		subscription: SubscriptionInfo,
+		notification: Box<Notification>
+	},
+	/// Twitch revoked a subscription, e.g. because the user revoked the app's authorization. The reason is given in
+	/// `subscription.status`.
+	Revocation { subscription: SubscriptionInfo }
+}
+
+pin_project! {
+	/// A connection to the EventSub WebSocket.
+	///
+	/// Twitch closes the connection if no subscriptions are created within 10 seconds of connecting, so call
+	/// [`EventSub::subscribe`] soon after [`EventSub::connect`]. The connection transparently follows
+	/// `session_reconnect` messages; subscriptions carry over to the new session, and notifications still in flight on
+	/// the old connection are delivered before those from the new one.
+	pub struct EventSub<E: RequestExecutor> {
+		client: helix::Client<E>,
+		session_id: Arc<Mutex<String>>,
+		#[pin]
+		stream: BoxStream<'static, Result<Event, EventSubError<E>>>
+	}
+}
+
+impl<E: RequestExecutor> EventSub<E> {
+	/// Connects to Twitch's EventSub WebSocket server.
+	pub async fn connect(client: helix::Client<E>) -> Result<Self, EventSubError<E>> {
+		Self::connect_to(client, EVENTSUB_WEBSOCKET_URL).await
+	}
+
+	/// Connects to an EventSub WebSocket server at a different URL, e.g. the
+	/// [Twitch CLI](https://dev.twitch.tv/docs/cli/websocket-event-command/)'s mock server.
+	pub async fn connect_to(client: helix::Client<E>, url: impl AsRef<str>) -> Result<Self, EventSubError<E>> {
+		let (mut socket, _) = tokio_tungstenite::connect_async(url.as_ref()).await?;
+		let session = wait_for_welcome::<E>(&mut socket).await?;
+		let keepalive_timeout = keepalive_timeout(&session);
+		let session_id = Arc::new(Mutex::new(session.id));
+
+		Ok(Self {
+			client,
+			session_id: Arc::clone(&session_id),
+			stream: Box::pin(try_async_stream(move |yielder| async move {
+				let mut socket = socket;
+				let mut keepalive_timeout = keepalive_timeout;
+				// After a `session_reconnect`, the old connection is read until Twitch closes it (or until its keepalive
+				// timeout passes) before moving on to the new one, so notifications still in flight on it aren't lost.
+				let mut draining: Option<(Socket, BoxFuture<'static, ()>)> = None;
+				loop {
+					let message = match &mut draining {
+						Some((old_socket, deadline)) => match select(old_socket.next(), deadline.as_mut()).await {
+							Either::Left((message, _)) => match parse_message::<E>(message) {
+								Ok(Some(message)) => message,
+								Ok(None) => continue,
+								Err(_) => {
+									draining = None;
+									continue;
+								}
+							},
+							Either::Right(_) => {
+								if let Some((mut old_socket, _)) = draining.take() {
+									let _ = old_socket.close(None).await;
+								}
+								continue;
+							}
+						},
+						None => {
+							let timeout = E::sleep(keepalive_timeout);
+							pin_mut!(timeout);
+							let message = match select(socket.next(), timeout).await {
+								Either::Left((message, _)) => message,
+								Either::Right(_) => return Err(EventSubError::KeepaliveTimeout)
+							};
+							let Some(message) = parse_message(message)? else {
+								continue;
+							};
+							message
+						}
+					};
+
+					match message.metadata.message_type.as_str() {
+						"notification" => {
+							let payload: NotificationPayload = simd_json::serde::from_owned_value(message.payload)?;
+							let notification = Notification::parse(&payload.subscription.kind, &payload.subscription.version, payload.event);
+							yielder
+								.y(Event::Notification {
+									message_id: message.metadata.message_id,
+									subscription: payload.subscription,
+									notification: Box::new(notification)
+								})
+								.await;
+						}
+						"revocation" => {
+							let payload: RevocationPayload = simd_json::serde::from_owned_value(message.payload)?;
+							yielder.y(Event::Revocation { subscription: payload.subscription }).await;
+						}
+						// Only the current connection can ask us to reconnect, not one being drained.
+						"session_reconnect" if draining.is_none() => {
+							let payload: SessionPayload = simd_json::serde::from_owned_value(message.payload)?;
+							let Some(reconnect_url) = payload.session.reconnect_url else {
+								return Err(EventSubError::UnexpectedMessage);
+							};
+							// The old connection stays open until the new one is welcomed, and the new session inherits
+							// all of the old session's subscriptions.
+							let (mut new_socket, _) = tokio_tungstenite::connect_async(reconnect_url.as_str()).await?;
+							let session = wait_for_welcome::<E>(&mut new_socket).await?;
+							let old_socket = std::mem::replace(&mut socket, new_socket);
+							draining = Some((old_socket, Box::pin(E::sleep(keepalive_timeout))));
+							keepalive_timeout = self::keepalive_timeout(&session);
+							*session_id.lock().unwrap_or_else(PoisonError::into_inner) = session.id;
+						}
+						_ => {}
+					}
+				}
+			}))
+		})
+	}
+
+	/// The ID of the current WebSocket session.
+	pub fn session_id(&self) -> String {
+		self.session_id.lock().unwrap_or_else(PoisonError::into_inner).clone()
+	}
+
+	/// Creates a subscription delivered over this connection.
+	pub async fn subscribe(&self, subscription: &Subscription) -> Result<SubscriptionInfo, EventSubError<E>> {
+		let session_id = self.session_id();
+		let response: DataResponse<SubscriptionInfo> = self
+			.client
+			.send_json(
+				Method::POST,
+				"/eventsub/subscriptions",
+				&CreateSubscriptionRequest {
+					kind: &subscription.kind,
+					version: &subscription.version,
+					condition: &subscription.condition,
+					transport: WebSocketTransport {
+						method: "websocket",
+						session_id: &session_id
+					}
+				}
+			)
+			.await?;
+		response.data.into_iter().next().ok_or(EventSubError::EmptyResponse)
+	}
+
+	/// Deletes a subscription by its [ID](SubscriptionInfo::id).
+	pub async fn unsubscribe(&self, id: impl AsRef<str>) -> Result<(), EventSubError<E>> {
+		self.client
+			.send_empty(Method::DELETE, &format!("/eventsub/subscriptions?id={}", id.as_ref()))
+			.await?;
+		Ok(())
+	}
+
+	/// The Helix client used to create subscriptions.
+	pub fn client(&self) -> &helix::Client<E> {
+		&self.client
+	}
+}
+
+impl<E: RequestExecutor> Stream for EventSub<E> {
+	type Item = Result<Event, EventSubError<E>>;
+
+	fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> task::Poll<Option<Self::Item>> {
+		self.project().stream.poll_next(cx)
+	}
+}
+
+fn keepalive_timeout(session: &Session) -> Duration {
+	Duration::from_secs(session.keepalive_timeout_seconds.unwrap_or(10)) + KEEPALIVE_GRACE
+}
+
+/// Parses a WebSocket frame into an EventSub message, returning `None` for control frames.
+fn parse_message<E: RequestExecutor>(message: Option<Result<WsMessage, tungstenite::Error>>) -> Result<Option<WebSocketMessage>, EventSubError<E>> {
+	match message {
+		Some(Ok(WsMessage::Text(text))) => {
+			let mut bytes = text.as_bytes().to_vec();
+			Ok(Some(simd_json::serde::from_slice(&mut bytes)?))
+		}
+		Some(Ok(WsMessage::Close(frame))) => Err(EventSubError::Closed {
+			code: frame.as_ref().map(|frame| frame.code.into()),
+			reason: frame.map(|frame| frame.reason.to_string()).unwrap_or_default()
+		}),
+		Some(Ok(_)) => Ok(None),
+		Some(Err(e)) => Err(EventSubError::WebSocket(e)),
+		None => Err(EventSubError::Closed { code: None, reason: String::new() })
+	}
+}
+
+async fn wait_for_welcome<E: RequestExecutor>(socket: &mut Socket) -> Result<Session, EventSubError<E>> {
+	let timeout = E::sleep(WELCOME_TIMEOUT);
+	pin_mut!(timeout);
+	loop {
+		let message = match select(socket.next(), timeout.as_mut()).await {
+			Either::Left((message, _)) => message,
+			Either::Right(_) => return Err(EventSubError::KeepaliveTimeout)
+		};
+		let Some(message) = parse_message(message)? else {
+			continue;
+		};
+		if message.metadata.message_type != "session_welcome" {
+			return Err(EventSubError::UnexpectedMessage);
+		}
+		let payload: SessionPayload = simd_json::serde::from_owned_value(message.payload)?;
+		return Ok(payload.session);
+	}
+}
+
+pub enum EventSubError<E: RequestExecutor> {
+	WebSocket(tungstenite::Error),
+	Deserialize(simd_json::Error),
+	Helix(HelixError<E>),
+	/// The server sent a message that isn't valid at this point in the session, e.g. a notification before the
+	/// `session_welcome` message.
+	UnexpectedMessage,
+	/// No message was received within the session's keepalive timeout.
+	KeepaliveTimeout,
+	/// Creating a subscription succeeded, but the response contained no subscription.
+	EmptyResponse,
+	/// The server closed the connection. See the [close code reference](https://dev.twitch.tv/docs/eventsub/handling-websocket-events/#close-message)
+	/// for the meaning of each `code`.
+	Closed {
+		code: Option<u16>,
+		reason: String
+	}
+}
+
+// `#[derive(Debug)]` would require `E::Response: Debug` via `HelixError`.
+impl<E: RequestExecutor + fmt::Debug> fmt::Debug for EventSubError<E> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::WebSocket(e) => f.debug_tuple("WebSocket").field(e).finish(),
+			Self::Deserialize(e) => f.debug_tuple("Deserialize").field(e).finish(),
+			Self::Helix(HelixError::Api { status_code, message }) => f
+				.debug_struct("Helix")
+				.field("status_code", status_code)
+				.field("message", message)
+				.finish(),
+			Self::Helix(e) => f.debug_tuple("Helix").field(&format_args!("{e}")).finish(),
+			Self::UnexpectedMessage => f.write_str("UnexpectedMessage"),
+			Self::KeepaliveTimeout => f.write_str("KeepaliveTimeout"),
+			Self::EmptyResponse => f.write_str("EmptyResponse"),
+			Self::Closed { code, reason } => f.debug_struct("Closed").field("code", code).field("reason", reason).finish()
+		}
+	}
+}
+
+impl<E: RequestExecutor> From<tungstenite::Error> for EventSubError<E> {
+	fn from(e: tungstenite::Error) -> Self {
+		Self::WebSocket(e)
+	}
+}
+impl<E: RequestExecutor> From<simd_json::Error> for EventSubError<E> {
+	fn from(e: simd_json::Error) -> Self {
+		Self::Deserialize(e)
+	}
+}
+impl<E: RequestExecutor> From<HelixError<E>> for EventSubError<E> {
+	fn from(e: HelixError<E>) -> Self {
+		Self::Helix(e)
+	}
+}
+
+impl<E: RequestExecutor> fmt::Display for EventSubError<E> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::WebSocket(e) => f.write_fmt(format_args!("websocket error: {e}")),
+			Self::Deserialize(e) => f.write_fmt(format_args!("failed to deserialize message: {e}")),
+			Self::Helix(e) => fmt::Display::fmt(e, f),
+			Self::UnexpectedMessage => f.write_str("received unexpected message"),
+			Self::KeepaliveTimeout => f.write_str("connection timed out"),
+			Self::EmptyResponse => f.write_str("subscription response was empty"),
+			Self::Closed { code: Some(code), reason } => f.write_fmt(format_args!("connection closed ({code}): {reason}")),
+			Self::Closed { code: None, .. } => f.write_str("connection closed")
+		}
+	}
+}
+
+impl<E: RequestExecutor + fmt::Debug> StdError for EventSubError<E>
+where
+	E::Response: fmt::Debug
+{
+	fn source(&self) -> Option<&(dyn StdError + 'static)> {
+		match self {
+			Self::WebSocket(e) => Some(e),
+			Self::Deserialize(e) => Some(e),
+			Self::Helix(e) => Some(e),
+			_ => None
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Notification;
+
+	#[test]
+	fn malformed_notifications_fall_back_to_other() {
+		let mut event = br#"{"id":"1243456","broadcaster_user_id":"1337"}"#.to_vec();
+		let event = simd_json::to_owned_value(&mut event).unwrap();
+		assert!(matches!(Notification::parse("channel.poll.begin", "1", event), Notification::Other(_)));
+	}
+}
@@ -0,0 +1,259 @@
+// Copyright 2026 pyke.io
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use simd_json::OwnedValue;
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct WebSocketMessage {
+	pub metadata: MessageMetadata,
+	pub payload: OwnedValue
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct MessageMetadata {
+	pub message_id: String,
+	pub message_type: String
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct SessionPayload {
+	pub session: Session
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct Session {
+	pub id: String,
+	pub keepalive_timeout_seconds: Option<u64>,
+	pub reconnect_url: Option<String>
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct NotificationPayload {
+	pub subscription: SubscriptionInfo,
+	pub event: OwnedValue
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct RevocationPayload {
+	pub subscription: SubscriptionInfo
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct CreateSubscriptionRequest<'s> {
+	#[serde(rename = "type")]
+	pub kind: &'s str,
+	pub version: &'s str,
+	pub condition: &'s BTreeMap<String, String>,
+	pub transport: WebSocketTransport<'s>
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct WebSocketTransport<'s> {
+	pub method: &'static str,
+	pub session_id: &'s str
+}
+
+/// An EventSub subscription, as returned by Twitch.
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct SubscriptionInfo {
+	pub id: String,
+	/// The subscription's status, e.g. `enabled`, or the reason it was revoked, e.g. `authorization_revoked` or
+	/// `user_removed`.
+	pub status: String,
+	#[serde(rename = "type")]
+	pub kind: String,
+	pub version: String,
+	pub condition: BTreeMap<String, String>,
+	#[serde(default)]
+	pub cost: u32,
+	pub created_at: String
+}
+
+/// A viewer redeemed a channel point reward.
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ChannelPointsRedemption {
+	/// The redemption's ID, used to fulfill or cancel it via the API.
+	pub id: String,
+	pub broadcaster_user_id: String,
+	pub broadcaster_user_login: String,
+	pub broadcaster_user_name: String,
+	pub user_id: String,
+	pub user_login: String,
+	pub user_name: String,
+	/// The text the viewer entered, if the reward requires it.
+	#[serde(default)]
+	pub user_input: String,
+	/// One of `unfulfilled`, `fulfilled`, `canceled` or `unknown`.
+	pub status: String,
+	pub reward: Reward,
+	pub redeemed_at: String
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Reward {
+	pub id: String,
+	pub title: String,
+	pub cost: u32,
+	#[serde(default)]
+	pub prompt: String
+}
+
+/// A poll's state. Sent when a poll begins, whenever its votes change, and when it ends.
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Poll {
+	pub id: String,
+	pub broadcaster_user_id: String,
+	pub broadcaster_user_login: String,
+	pub broadcaster_user_name: String,
+	pub title: String,
+	pub choices: Vec<PollChoice>,
+	pub channel_points_voting: Option<ChannelPointsVoting>,
+	pub started_at: String,
+	/// When the poll will end; only present while the poll is running.
+	pub ends_at: Option<String>,
+	/// When the poll ended; only present once the poll ends.
+	pub ended_at: Option<String>,
+	/// One of `completed`, `archived` or `terminated`; only present once the poll ends.
+	pub status: Option<String>
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct PollChoice {
+	pub id: String,
+	pub title: String,
+	/// The total number of votes for this choice, including those cast with channel points.
+	#[serde(default)]
+	pub votes: u32,
+	#[serde(default)]
+	pub channel_points_votes: u32
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ChannelPointsVoting {
+	pub is_enabled: bool,
+	pub amount_per_vote: u32
+}
+
+/// A prediction's state. Sent when a prediction begins, whenever users predict, when it locks, and when it ends.
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Prediction {
+	pub id: String,
+	pub broadcaster_user_id: String,
+	pub broadcaster_user_login: String,
+	pub broadcaster_user_name: String,
+	pub title: String,
+	pub outcomes: Vec<PredictionOutcome>,
+	pub started_at: String,
+	pub locks_at: Option<String>,
+	pub locked_at: Option<String>,
+	pub ended_at: Option<String>,
+	/// The ID of the outcome that won; only present once the prediction is resolved.
+	pub winning_outcome_id: Option<String>,
+	/// One of `resolved` or `canceled`; only present once the prediction ends.
+	pub status: Option<String>
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct PredictionOutcome {
+	pub id: String,
+	pub title: String,
+	/// Either `blue` or `pink`.
+	pub color: String,
+	#[serde(default)]
+	pub users: u32,
+	#[serde(default)]
+	pub channel_points: u64
+}
+
+/// A hype train's state. Sent when a hype train begins, whenever it progresses, and when it ends.
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct HypeTrain {
+	pub id: String,
+	pub broadcaster_user_id: String,
+	pub broadcaster_user_login: String,
+	pub broadcaster_user_name: String,
+	pub level: u32,
+	pub total: u64,
+	/// Points contributed towards the current level; not present once the hype train ends.
+	pub progress: Option<u64>,
+	/// Points required to reach the next level; not present once the hype train ends.
+	pub goal: Option<u64>,
+	#[serde(default)]
+	pub top_contributions: Vec<HypeTrainContribution>,
+	pub started_at: String,
+	pub expires_at: Option<String>,
+	pub ended_at: Option<String>
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct HypeTrainContribution {
+	pub user_id: String,
+	pub user_login: String,
+	pub user_name: String,
+	/// One of `bits`, `subscription` or `other`.
+	#[serde(rename = "type")]
+	pub kind: String,
+	pub total: u64
+}
+
+/// A user followed the channel.
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Follow {
+	pub user_id: String,
+	pub user_login: String,
+	pub user_name: String,
+	pub broadcaster_user_id: String,
+	pub broadcaster_user_login: String,
+	pub broadcaster_user_name: String,
+	pub followed_at: String
+}
+
+/// A shared chat session's state. Sent when the channel joins a session, when its participants change, and when the
+/// channel leaves it.
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct SharedChatSession {
+	pub session_id: String,
+	pub broadcaster_user_id: String,
+	pub broadcaster_user_login: String,
+	pub broadcaster_user_name: String,
+	pub host_broadcaster_user_id: String,
+	pub host_broadcaster_user_login: String,
+	pub host_broadcaster_user_name: String,
+	/// The channels in the session; empty when the session ends.
+	#[serde(default)]
+	pub participants: Vec<SharedChatParticipant>
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct SharedChatParticipant {
+	pub broadcaster_user_id: String,
+	pub broadcaster_user_login: String,
+	pub broadcaster_user_name: String
+}
@@ -0,0 +1,181 @@
+// Copyright 2026 pyke.io
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use http::Method;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{Client, DataResponse, HelixError, RequestExecutor};
+use crate::twitch::AnnouncementColor;
+
+/// A channel's chat settings, as returned by [`Client::chat_settings`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ChatSettings {
+	pub broadcaster_id: String,
+	pub emote_mode: bool,
+	pub follower_mode: bool,
+	/// How long, in minutes, users must have followed the channel for to chat when `follower_mode` is enabled.
+	pub follower_mode_duration: Option<u32>,
+	/// Only present if a moderator ID was provided.
+	pub moderator_id: Option<String>,
+	/// Whether messages from non-moderators are delayed. Only present if a moderator ID was provided.
+	pub non_moderator_chat_delay: Option<bool>,
+	/// The delay in seconds. Only present if a moderator ID was provided.
+	pub non_moderator_chat_delay_duration: Option<u32>,
+	pub slow_mode: bool,
+	/// How long, in seconds, users must wait between messages when `slow_mode` is enabled.
+	pub slow_mode_wait_time: Option<u32>,
+	pub subscriber_mode: bool,
+	pub unique_chat_mode: bool
+}
+
+/// Changes to make to a channel's chat settings via [`Client::update_chat_settings`]. Settings left as `None` are
+/// unchanged.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct ChatSettingsUpdate {
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub emote_mode: Option<bool>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub follower_mode: Option<bool>,
+	/// In minutes, between 0 and 129600 (3 months).
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub follower_mode_duration: Option<u32>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub non_moderator_chat_delay: Option<bool>,
+	/// In seconds; one of 2, 4 or 6.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub non_moderator_chat_delay_duration: Option<u32>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub slow_mode: Option<bool>,
+	/// In seconds, between 3 and 120.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub slow_mode_wait_time: Option<u32>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub subscriber_mode: Option<bool>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub unique_chat_mode: Option<bool>
+}
+
+#[derive(Serialize)]
+struct AnnouncementBody<'b> {
+	message: &'b str,
+	color: &'b str
+}
+
+#[derive(Serialize)]
+struct ChatMessageBody<'b> {
+	broadcaster_id: String,
+	sender_id: String,
+	message: &'b str,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	reply_parent_message_id: Option<String>
+}
+
+/// The result of sending a message via [`Client::send_chat_message`].
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct SentChatMessage {
+	/// The message's ID. Empty if the message was dropped.
+	pub message_id: String,
+	pub is_sent: bool,
+	/// Why the message was dropped, if it wasn't sent.
+	pub drop_reason: Option<DropReason>
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct DropReason {
+	/// A code like `msg_duplicate` or `msg_slowmode`; see [`SendRejection`](crate::twitch::SendRejection) for the
+	/// common ones.
+	pub code: String,
+	pub message: String
+}
+
+/// Chat endpoints.
+impl<E: RequestExecutor> Client<E> {
+	/// Gets a channel's chat settings. If `moderator_id` is provided, the token must belong to that moderator and have
+	/// the `moderator:read:chat_settings` scope, and the response will include the non-moderator chat delay.
+	pub async fn chat_settings(&self, broadcaster_id: u64, moderator_id: Option<u64>) -> Result<ChatSettings, HelixError<E>> {
+		let path = match moderator_id {
+			Some(moderator_id) => format!("/chat/settings?broadcaster_id={broadcaster_id}&moderator_id={moderator_id}"),
+			None => format!("/chat/settings?broadcaster_id={broadcaster_id}")
+		};
+		let response: DataResponse<ChatSettings> = self.get_json(&path).await?;
+		response.into_first()
+	}
+
+	/// Updates a channel's chat settings, returning the new settings. Requires the `moderator:manage:chat_settings`
+	/// scope.
+	pub async fn update_chat_settings(&self, broadcaster_id: u64, moderator_id: u64, update: &ChatSettingsUpdate) -> Result<ChatSettings, HelixError<E>> {
+		let response: DataResponse<ChatSettings> = self
+			.send_json(Method::PATCH, &format!("/chat/settings?broadcaster_id={broadcaster_id}&moderator_id={moderator_id}"), update)
+			.await?;
+		response.into_first()
+	}
+
+	/// Sends a shoutout for `to_broadcaster_id` in `from_broadcaster_id`'s chat. Requires the
+	/// `moderator:manage:shoutouts` scope.
+	///
+	/// The broadcaster must be live, and shoutouts are limited to one every 2 minutes per channel and one every hour
+	/// per target channel.
+	pub async fn send_shoutout(&self, from_broadcaster_id: u64, to_broadcaster_id: u64, moderator_id: u64) -> Result<(), HelixError<E>> {
+		self.send_empty(
+			Method::POST,
+			&format!("/chat/shoutouts?from_broadcaster_id={from_broadcaster_id}&to_broadcaster_id={to_broadcaster_id}&moderator_id={moderator_id}")
+		)
+		.await
+	}
+
+	/// Sends an announcement to a channel's chat. Requires the `moderator:manage:announcements` scope.
+	pub async fn send_announcement(&self, broadcaster_id: u64, moderator_id: u64, message: &str, color: &AnnouncementColor) -> Result<(), HelixError<E>> {
+		let color = match color {
+			AnnouncementColor::Primary => "primary",
+			AnnouncementColor::Blue => "blue",
+			AnnouncementColor::Green => "green",
+			AnnouncementColor::Orange => "orange",
+			AnnouncementColor::Purple => "purple",
+			AnnouncementColor::Other(color) => color.as_str()
+		};
+		self.send_json_empty(
+			Method::POST,
+			&format!("/chat/announcements?broadcaster_id={broadcaster_id}&moderator_id={moderator_id}"),
+			&AnnouncementBody { message, color }
+		)
+		.await
+	}
+
+	/// Sends a chat message as `sender_id`, optionally as a reply. Requires the `user:write:chat` scope.
+	pub async fn send_chat_message(
+		&self,
+		broadcaster_id: u64,
+		sender_id: u64,
+		message: &str,
+		reply_to: Option<Uuid>
+	) -> Result<SentChatMessage, HelixError<E>> {
+		let response: DataResponse<SentChatMessage> = self
+			.send_json(
+				Method::POST,
+				"/chat/messages",
+				&ChatMessageBody {
+					broadcaster_id: broadcaster_id.to_string(),
+					sender_id: sender_id.to_string(),
+					message,
+					reply_parent_message_id: reply_to.map(|id| id.to_string())
+				}
+			)
+			.await?;
+		response.into_first()
+	}
+}
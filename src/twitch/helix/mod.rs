@@ -0,0 +1,204 @@
+// Copyright 2026 pyke.io
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A client for the [Twitch Helix API](https://dev.twitch.tv/docs/api/reference/).
+//!
+//! Like the YouTube client, requests are made through a user-provided [`RequestExecutor`], so you can choose your own
+//! HTTP stack (or point the client at a mock server in tests).
+
+use std::{error::Error as StdError, fmt};
+
+use bytes::Bytes;
+use http::{HeaderName, HeaderValue, Method, Request, Uri, header, request::Builder as RequestBuilder};
+use serde::{Serialize, de::DeserializeOwned};
+
+mod chat;
+pub use self::chat::{ChatSettings, ChatSettingsUpdate, DropReason, SentChatMessage};
+mod moderation;
+pub use self::moderation::{Ban, BanUser};
+mod whispers;
+pub use crate::executor::{RequestExecutor, Response};
+
+pub(crate) const HELIX_BASE_URL: &str = "https://api.twitch.tv/helix";
+
+/// A Helix API client.
+///
+/// Requests are authenticated with a [user access token](https://dev.twitch.tv/docs/authentication/#user-access-tokens)
+/// and the client ID of the application the token was issued to.
+#[derive(Debug, Clone)]
+pub struct Client<E> {
+	http_client: E,
+	base_url: String,
+	client_id: HeaderValue,
+	authorization: HeaderValue
+}
+
+impl<E: RequestExecutor> Client<E> {
+	/// Creates a client authenticated with the given client ID and user access token.
+	///
+	/// Returns an error if either contains characters that aren't allowed in an HTTP header.
+	pub fn new(executor: E, client_id: impl AsRef<str>, access_token: impl AsRef<str>) -> Result<Self, http::Error> {
+		Ok(Self {
+			http_client: executor,
+			base_url: HELIX_BASE_URL.to_owned(),
+			client_id: HeaderValue::from_str(client_id.as_ref())?,
+			authorization: HeaderValue::from_str(&format!("Bearer {}", access_token.as_ref()))?
+		})
+	}
+
+	/// Sends requests to a different base URL instead of `https://api.twitch.tv/helix`, e.g. to use a mock server.
+	pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+		self.base_url = base_url.into();
+		self.base_url.truncate(self.base_url.trim_end_matches('/').len());
+		self
+	}
+
+	pub(crate) fn base_request(&self, method: Method, path_and_query: &str) -> Result<RequestBuilder, HelixError<E>> {
+		let uri: Uri = format!("{}{path_and_query}", self.base_url)
+			.parse()
+			.map_err(|e| HelixError::BadRequest(http::Error::from(e)))?;
+		Ok(Request::builder()
+			.method(method)
+			.uri(uri)
+			.header(HeaderName::from_static("client-id"), self.client_id.clone())
+			.header(header::AUTHORIZATION, self.authorization.clone()))
+	}
+
+	pub(crate) async fn execute(&self, request: Request<Bytes>) -> Result<E::Response, HelixError<E>> {
+		let response = self.http_client.make_request(request).await.map_err(HelixError::Executor)?;
+		match response.status_code() {
+			200..=299 => Ok(response),
+			status_code => {
+				let mut body = response.recv_all().await.map_err(HelixError::Receive)?;
+				let message = simd_json::serde::from_slice::<ApiError>(&mut body).map(|e| e.message).unwrap_or_default();
+				Err(HelixError::Api { status_code, message })
+			}
+		}
+	}
+
+	fn json_request<B: Serialize>(&self, method: Method, path_and_query: &str, body: &B) -> Result<Request<Bytes>, HelixError<E>> {
+		Ok(self
+			.base_request(method, path_and_query)?
+			.header(header::CONTENT_TYPE, HeaderValue::from_static("application/json"))
+			.body(simd_json::to_vec(body)?.into())?)
+	}
+
+	pub(crate) async fn get_json<R: DeserializeOwned>(&self, path_and_query: &str) -> Result<R, HelixError<E>> {
+		let request = self.base_request(Method::GET, path_and_query)?.body(Bytes::new())?;
+		let mut response = self.execute(request).await?.recv_all().await.map_err(HelixError::Receive)?;
+		Ok(simd_json::serde::from_slice(&mut response)?)
+	}
+
+	pub(crate) async fn send_json<B: Serialize, R: DeserializeOwned>(&self, method: Method, path_and_query: &str, body: &B) -> Result<R, HelixError<E>> {
+		let request = self.json_request(method, path_and_query, body)?;
+		let mut response = self.execute(request).await?.recv_all().await.map_err(HelixError::Receive)?;
+		Ok(simd_json::serde::from_slice(&mut response)?)
+	}
+
+	/// Like [`Client::send_json`], but for endpoints that respond with `204 No Content`.
+	pub(crate) async fn send_json_empty<B: Serialize>(&self, method: Method, path_and_query: &str, body: &B) -> Result<(), HelixError<E>> {
+		let request = self.json_request(method, path_and_query, body)?;
+		self.execute(request).await?;
+		Ok(())
+	}
+
+	pub(crate) async fn send_empty(&self, method: Method, path_and_query: &str) -> Result<(), HelixError<E>> {
+		let request = self.base_request(method, path_and_query)?.body(Bytes::new())?;
+		self.execute(request).await?;
+		Ok(())
+	}
+}
+
+#[derive(serde::Deserialize)]
+pub(crate) struct ApiError {
+	#[serde(default)]
+	pub message: String
+}
+
+/// The `{ "data": [...] }` wrapper that most Helix responses come in.
+#[derive(Debug, serde::Deserialize)]
+pub(crate) struct DataResponse<T> {
+	pub data: Vec<T>
+}
+
+impl<T> DataResponse<T> {
+	/// Takes the single object returned by endpoints that act on one resource.
+	pub(crate) fn into_first<E: RequestExecutor>(self) -> Result<T, HelixError<E>> {
+		self.data.into_iter().next().ok_or(HelixError::EmptyResponse)
+	}
+}
+
+#[derive(Debug)]
+pub enum HelixError<E: RequestExecutor> {
+	BadRequest(http::Error),
+	Deserialize(simd_json::Error),
+	Executor(E::Error),
+	Receive(<E::Response as Response>::Error),
+	/// The API responded with a non-2xx status code.
+	Api {
+		status_code: u16,
+		message: String
+	},
+	/// The API responded successfully, but didn't include the expected object.
+	EmptyResponse
+}
+
+impl<E: RequestExecutor> HelixError<E> {
+	/// Returns the HTTP status code returned by the API, if the request made it that far.
+	pub fn status_code(&self) -> Option<u16> {
+		match self {
+			Self::Api { status_code, .. } => Some(*status_code),
+			_ => None
+		}
+	}
+}
+
+impl<E: RequestExecutor> From<simd_json::Error> for HelixError<E> {
+	fn from(e: simd_json::Error) -> Self {
+		Self::Deserialize(e)
+	}
+}
+impl<E: RequestExecutor> From<http::Error> for HelixError<E> {
+	fn from(e: http::Error) -> Self {
+		Self::BadRequest(e)
+	}
+}
+
+impl<E: RequestExecutor> fmt::Display for HelixError<E> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::BadRequest(e) => f.write_fmt(format_args!("accidentally built malformed request: {e}")),
+			Self::Deserialize(e) => f.write_fmt(format_args!("failed to (de)serialize body: {e}")),
+			Self::Executor(e) => f.write_fmt(format_args!("failed to execute request: {e}")),
+			Self::Receive(e) => f.write_fmt(format_args!("failed to receive response: {e}")),
+			Self::Api { status_code, message } => f.write_fmt(format_args!("helix returned status {status_code}: {message}")),
+			Self::EmptyResponse => f.write_str("helix response was empty")
+		}
+	}
+}
+
+impl<E: RequestExecutor + fmt::Debug> StdError for HelixError<E>
+where
+	E::Response: fmt::Debug
+{
+	fn source(&self) -> Option<&(dyn StdError + 'static)> {
+		match self {
+			Self::BadRequest(e) => Some(e),
+			Self::Deserialize(e) => Some(e),
+			Self::Executor(e) => Some(e),
+			Self::Receive(e) => Some(e),
+			_ => None
+		}
+	}
+}
@@ -0,0 +1,131 @@
+// Copyright 2026 pyke.io
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use http::Method;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{Client, DataResponse, HelixError, RequestExecutor};
+
+/// The longest timeout Twitch allows, in seconds (2 weeks).
+const MAX_TIMEOUT_SECS: u64 = 1_209_600;
+
+/// A ban or timeout to apply via [`Client::ban_user`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BanUser {
+	pub user_id: u64,
+	/// How long to time the user out for, between 1 second and 2 weeks. `None` bans the user permanently.
+	pub duration: Option<Duration>,
+	pub reason: Option<String>
+}
+
+impl BanUser {
+	/// Bans a user permanently.
+	pub fn permanent(user_id: u64) -> Self {
+		Self {
+			user_id,
+			duration: None,
+			reason: None
+		}
+	}
+
+	/// Times a user out. The duration is rounded up to whole seconds, and clamped to at most 2 weeks.
+	pub fn timeout(user_id: u64, duration: Duration) -> Self {
+		Self {
+			user_id,
+			duration: Some(duration),
+			reason: None
+		}
+	}
+
+	pub fn with_reason(mut self, reason: impl Into<String>) -> Self {
+		self.reason = Some(reason.into());
+		self
+	}
+}
+
+#[derive(Serialize)]
+struct BanUserBody<'b> {
+	data: BanUserData<'b>
+}
+
+#[derive(Serialize)]
+struct BanUserData<'b> {
+	user_id: String,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	duration: Option<u64>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	reason: Option<&'b str>
+}
+
+/// A ban or timeout applied via [`Client::ban_user`].
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Ban {
+	pub broadcaster_id: String,
+	pub moderator_id: String,
+	pub user_id: String,
+	pub created_at: String,
+	/// When the timeout ends, or `None` if the ban is permanent.
+	pub end_time: Option<String>
+}
+
+/// Moderation endpoints. These require the token's user to be the broadcaster or one of their moderators; pass the
+/// token user's ID as `moderator_id`.
+impl<E: RequestExecutor> Client<E> {
+	/// Bans or times out a user. Requires the `moderator:manage:banned_users` scope.
+	pub async fn ban_user(&self, broadcaster_id: u64, moderator_id: u64, ban: &BanUser) -> Result<Ban, HelixError<E>> {
+		let response: DataResponse<Ban> = self
+			.send_json(
+				Method::POST,
+				&format!("/moderation/bans?broadcaster_id={broadcaster_id}&moderator_id={moderator_id}"),
+				&BanUserBody {
+					data: BanUserData {
+						user_id: ban.user_id.to_string(),
+						duration: ban.duration.map(timeout_secs),
+						reason: ban.reason.as_deref()
+					}
+				}
+			)
+			.await?;
+		response.into_first()
+	}
+
+	/// Removes a ban or timeout. Requires the `moderator:manage:banned_users` scope.
+	pub async fn unban_user(&self, broadcaster_id: u64, moderator_id: u64, user_id: u64) -> Result<(), HelixError<E>> {
+		self.send_empty(Method::DELETE, &format!("/moderation/bans?broadcaster_id={broadcaster_id}&moderator_id={moderator_id}&user_id={user_id}"))
+			.await
+	}
+
+	/// Deletes a single chat message. Requires the `moderator:manage:chat_messages` scope.
+	pub async fn delete_message(&self, broadcaster_id: u64, moderator_id: u64, message_id: Uuid) -> Result<(), HelixError<E>> {
+		self.send_empty(Method::DELETE, &format!("/moderation/chat?broadcaster_id={broadcaster_id}&moderator_id={moderator_id}&message_id={message_id}"))
+			.await
+	}
+
+	/// Deletes all messages in the chat, like `/clear`. Requires the `moderator:manage:chat_messages` scope.
+	pub async fn clear_chat(&self, broadcaster_id: u64, moderator_id: u64) -> Result<(), HelixError<E>> {
+		self.send_empty(Method::DELETE, &format!("/moderation/chat?broadcaster_id={broadcaster_id}&moderator_id={moderator_id}"))
+			.await
+	}
+}
+
+/// Converts a timeout to the whole number of seconds Twitch expects. Rounding down would turn a sub-second timeout
+/// into `0`, which Twitch rejects.
+fn timeout_secs(duration: Duration) -> u64 {
+	let secs = duration.as_secs() + u64::from(duration.subsec_nanos() > 0);
+	secs.clamp(1, MAX_TIMEOUT_SECS)
+}
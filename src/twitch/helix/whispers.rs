@@ -0,0 +1,38 @@
+// Copyright 2026 pyke.io
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use http::Method;
+use serde::Serialize;
+
+use super::{Client, HelixError, RequestExecutor};
+
+#[derive(Serialize)]
+struct WhisperBody<'b> {
+	message: &'b str
+}
+
+/// Whisper endpoints.
+impl<E: RequestExecutor> Client<E> {
+	/// Sends a whisper from `from_user_id` to `to_user_id`. Requires the `user:manage:whispers` scope, and the sender
+	/// must have a verified phone number.
+	///
+	/// Whispers can no longer be sent over IRC, so this is the only way to reply to a
+	/// [`ChatEvent::Whisper`](crate::twitch::ChatEvent::Whisper). Messages are truncated to 500 characters if the
+	/// recipient hasn't whispered the sender before, or 10,000 characters if they have, and Twitch limits how many
+	/// users can be whispered per day.
+	pub async fn send_whisper(&self, from_user_id: u64, to_user_id: u64, message: &str) -> Result<(), HelixError<E>> {
+		self.send_json_empty(Method::POST, &format!("/whispers?from_user_id={from_user_id}&to_user_id={to_user_id}"), &WhisperBody { message })
+			.await
+	}
+}
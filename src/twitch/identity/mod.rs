@@ -0,0 +1,159 @@
+// Copyright 2024 pyke.io
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{convert::Infallible, error::Error as StdError, future::Future, pin::Pin};
+
+use super::Error;
+
+#[cfg(feature = "twitch-helix")]
+mod refreshing;
+#[cfg(feature = "twitch-helix")]
+pub use self::refreshing::{RefreshingIdentity, UserToken};
+
+/// The username and OAuth access token to log in with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Credentials {
+	pub username: String,
+	/// The OAuth access token, without the `oauth:` prefix. `None` logs in anonymously.
+	pub token: Option<String>
+}
+
+impl Credentials {
+	pub fn new(username: impl Into<String>, token: impl Into<String>) -> Self {
+		Self {
+			username: username.into(),
+			token: Some(token.into())
+		}
+	}
+
+	pub fn anonymous(username: impl Into<String>) -> Self {
+		Self {
+			username: username.into(),
+			token: None
+		}
+	}
+}
+
+impl TwitchIdentity for Credentials {
+	type Error = Infallible;
+
+	async fn credentials(&self) -> Result<Credentials, Self::Error> {
+		Ok(self.clone())
+	}
+}
+
+/// Represents a type that can be used to identify the client.
+///
+/// Credentials are requested before every connection attempt, including reconnects, so an identity can hand out a
+/// fresh token each time; see [`RefreshingIdentity`].
+pub trait TwitchIdentity: Send + Sync + 'static {
+	type Error: StdError + Send + Sync + 'static;
+
+	/// Returns the credentials to log in with.
+	fn credentials(&self) -> impl Future<Output = Result<Credentials, Self::Error>> + Send;
+
+	/// Called when Twitch rejects the credentials with a `Login authentication failed` notice. Returns whether the
+	/// next call to [`TwitchIdentity::credentials`] will return different credentials; if so, and
+	/// [reconnection](super::Chat::with_reconnect) is enabled, [`Chat`](super::Chat) reconnects with them. Otherwise,
+	/// the stream yields an error and ends.
+	fn invalidate(&self) -> bool {
+		false
+	}
+}
+
+/// Anonymous identity with no authentication. It will not show up in the chatters list, and its capabilities are
+/// limited. This can really only be used to receive chat events.
+#[derive(Debug, Clone, Copy)]
+pub struct Anonymous;
+
+impl TwitchIdentity for Anonymous {
+	type Error = Infallible;
+
+	async fn credentials(&self) -> Result<Credentials, Self::Error> {
+		Ok(Credentials::anonymous("justinfan24340"))
+	}
+}
+
+/// Authenticated identity with a username and OAuth access token.
+///
+/// For more information on OAuth scopes and how to acquire an OAuth token, see the Twitch documentation: <https://dev.twitch.tv/docs/irc/authenticate-bot/>
+///
+/// Note that the account will show up in the chatters list. If you are indiscriminately crawling stream chats, please
+/// use [`Anonymous`] instead.
+///
+/// ```no_run
+/// use brainrot::twitch::{Authenticated, Chat};
+///
+/// # #[tokio::main]
+/// # async fn main() -> anyhow::Result<()> {
+/// let mut client = Chat::new("miyukiwei", Authenticated("yukifan4", "yfvzjqb705z12hrhy1zkwa9xt7v662")).await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// The username and token can be any string type, including borrowed ones; they're copied into [`Credentials`] when
+/// connecting. Use [`Authenticated::new`] for an owned identity that can be stored in a config struct.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Authenticated<U = String, T = String>(pub U, pub T);
+
+impl Authenticated {
+	pub fn new(username: impl Into<String>, token: impl Into<String>) -> Self {
+		Self(username.into(), token.into())
+	}
+}
+
+impl<U: AsRef<str>, T: AsRef<str>> IntoTwitchIdentity for Authenticated<U, T> {
+	type Identity = Credentials;
+
+	fn into_identity(self) -> Self::Identity {
+		Credentials::new(self.0.as_ref(), self.1.as_ref())
+	}
+}
+
+/// Conversion into a [`TwitchIdentity`], used by [`Chat::new`](super::Chat::new) and
+/// [`ChatBuilder::connect`](super::ChatBuilder::connect).
+///
+/// Every [`TwitchIdentity`] converts into itself. [`Authenticated`] converts into [`Credentials`], so it doesn't need
+/// to outlive the connection.
+pub trait IntoTwitchIdentity {
+	type Identity: TwitchIdentity;
+
+	fn into_identity(self) -> Self::Identity;
+}
+
+impl<I: TwitchIdentity> IntoTwitchIdentity for I {
+	type Identity = I;
+
+	fn into_identity(self) -> Self::Identity {
+		self
+	}
+}
+
+/// An object-safe [`TwitchIdentity`], so [`Chat`](super::Chat) can hold on to one for reconnecting.
+pub(crate) trait DynIdentity: Send + Sync {
+	fn credentials(&self) -> Pin<Box<dyn Future<Output = Result<Credentials, Error>> + Send + '_>>;
+
+	fn invalidate(&self) -> bool;
+}
+
+impl<I: TwitchIdentity> DynIdentity for I {
+	fn credentials(&self) -> Pin<Box<dyn Future<Output = Result<Credentials, Error>> + Send + '_>> {
+		Box::pin(async move { TwitchIdentity::credentials(self).await.map_err(|e| Error::Credentials(Box::new(e))) })
+	}
+
+	fn invalidate(&self) -> bool {
+		TwitchIdentity::invalidate(self)
+	}
+}
@@ -0,0 +1,288 @@
+// Copyright 2026 pyke.io
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+	fmt,
+	sync::{Mutex, PoisonError},
+	time::{Duration, Instant}
+};
+
+use bytes::Bytes;
+use http::{HeaderValue, Method, Request, header};
+
+use super::{Credentials, TwitchIdentity};
+use crate::{
+	executor::{RequestExecutor, Response},
+	twitch::helix::{ApiError, HelixError},
+	util::push_url_encoded
+};
+
+const TWITCH_TOKEN_URL: &str = "https://id.twitch.tv/oauth2/token";
+/// Tokens expiring within this long are refreshed before connecting.
+const REFRESH_MARGIN: Duration = Duration::from_secs(5 * 60);
+
+/// An OAuth access token and the refresh token used to renew it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UserToken {
+	pub access_token: String,
+	pub refresh_token: String
+}
+
+#[derive(Debug)]
+struct TokenState {
+	token: UserToken,
+	/// When the access token expires, if known.
+	expires_at: Option<Instant>,
+	/// Whether Twitch rejected the access token.
+	rejected: bool,
+	/// Incremented each time the access token is refreshed.
+	generation: u64,
+	/// The generation of the access token Twitch last rejected.
+	rejected_generation: Option<u64>
+}
+
+type RefreshCallback = Box<dyn Fn(&UserToken) + Send + Sync>;
+
+#[derive(serde::Deserialize)]
+struct RefreshResponse {
+	access_token: String,
+	refresh_token: String,
+	expires_in: Option<u64>
+}
+
+/// An identity that [refreshes](https://dev.twitch.tv/docs/authentication/refresh-tokens/) its access token when it
+/// is about to expire, or when Twitch rejects it.
+///
+/// Twitch may issue a new refresh token with each refresh. Use [`RefreshingIdentity::on_refresh`] to persist it, so
+/// the identity can be restored later.
+pub struct RefreshingIdentity<E: RequestExecutor> {
+	executor: E,
+	username: String,
+	client_id: String,
+	client_secret: Option<String>,
+	token_url: String,
+	state: Mutex<TokenState>,
+	on_refresh: Option<RefreshCallback>
+}
+
+impl<E: RequestExecutor> RefreshingIdentity<E> {
+	/// Creates an identity for `username` from a token issued to the application with the given client ID.
+	///
+	/// The expiry of the initial access token is unknown, so it is used until Twitch rejects it unless
+	/// [`RefreshingIdentity::with_expiry`] is called.
+	pub fn new(executor: E, username: impl Into<String>, client_id: impl Into<String>, token: UserToken) -> Self {
+		Self {
+			executor,
+			username: username.into(),
+			client_id: client_id.into(),
+			client_secret: None,
+			token_url: TWITCH_TOKEN_URL.to_owned(),
+			state: Mutex::new(TokenState {
+				token,
+				expires_at: None,
+				rejected: false,
+				generation: 0,
+				rejected_generation: None
+			}),
+			on_refresh: None
+		}
+	}
+
+	/// Sets the application's client secret. This is required for tokens issued via the authorization code flow, but
+	/// not for tokens issued to public clients via the device code flow.
+	pub fn with_client_secret(mut self, client_secret: impl Into<String>) -> Self {
+		self.client_secret = Some(client_secret.into());
+		self
+	}
+
+	/// Sets how long the initial access token is valid for, e.g. from the `expires_in` field of the response that
+	/// issued it.
+	pub fn with_expiry(self, expires_in: Duration) -> Self {
+		self.state.lock().unwrap_or_else(PoisonError::into_inner).expires_at = Some(Instant::now() + expires_in);
+		self
+	}
+
+	/// Refreshes tokens via a different URL instead of `https://id.twitch.tv/oauth2/token`, e.g. to use a mock server.
+	pub fn with_token_url(mut self, token_url: impl Into<String>) -> Self {
+		self.token_url = token_url.into();
+		self
+	}
+
+	/// Calls `callback` with the new tokens after each refresh.
+	pub fn on_refresh(mut self, callback: impl Fn(&UserToken) + Send + Sync + 'static) -> Self {
+		self.on_refresh = Some(Box::new(callback));
+		self
+	}
+
+	/// Returns the current tokens.
+	pub fn token(&self) -> UserToken {
+		self.state.lock().unwrap_or_else(PoisonError::into_inner).token.clone()
+	}
+
+	async fn refresh(&self, refresh_token: &str) -> Result<RefreshResponse, HelixError<E>> {
+		let mut body = String::from("grant_type=refresh_token&refresh_token=");
+		push_url_encoded(&mut body, refresh_token);
+		body.push_str("&client_id=");
+		push_url_encoded(&mut body, &self.client_id);
+		if let Some(client_secret) = &self.client_secret {
+			body.push_str("&client_secret=");
+			push_url_encoded(&mut body, client_secret);
+		}
+		let request = Request::builder()
+			.method(Method::POST)
+			.uri(&self.token_url)
+			.header(header::CONTENT_TYPE, HeaderValue::from_static("application/x-www-form-urlencoded"))
+			.body(Bytes::from(body))?;
+
+		let response = self.executor.make_request(request).await.map_err(HelixError::Executor)?;
+		let status_code = response.status_code();
+		let mut body = response.recv_all().await.map_err(HelixError::Receive)?;
+		if !(200..=299).contains(&status_code) {
+			let message = simd_json::serde::from_slice::<ApiError>(&mut body).map(|e| e.message).unwrap_or_default();
+			return Err(HelixError::Api { status_code, message });
+		}
+		Ok(simd_json::serde::from_slice(&mut body)?)
+	}
+}
+
+impl<E: RequestExecutor + fmt::Debug> fmt::Debug for RefreshingIdentity<E> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("RefreshingIdentity")
+			.field("executor", &self.executor)
+			.field("username", &self.username)
+			.field("client_id", &self.client_id)
+			.finish_non_exhaustive()
+	}
+}
+
+impl<E: RequestExecutor + fmt::Debug> TwitchIdentity for RefreshingIdentity<E>
+where
+	E::Response: fmt::Debug,
+	E::Error: Sync,
+	<E::Response as Response>::Error: Sync
+{
+	type Error = HelixError<E>;
+
+	async fn credentials(&self) -> Result<Credentials, Self::Error> {
+		let refresh_token = {
+			let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+			let expiring = state
+				.expires_at
+				.is_some_and(|expires_at| expires_at.saturating_duration_since(Instant::now()) < REFRESH_MARGIN);
+			if !state.rejected && !expiring {
+				return Ok(Credentials::new(&self.username, &state.token.access_token));
+			}
+			state.token.refresh_token.clone()
+		};
+
+		let response = self.refresh(&refresh_token).await?;
+		let token = UserToken {
+			access_token: response.access_token,
+			refresh_token: response.refresh_token
+		};
+		if let Some(callback) = &self.on_refresh {
+			callback(&token);
+		}
+
+		let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+		state.expires_at = response.expires_in.map(|expires_in| Instant::now() + Duration::from_secs(expires_in));
+		state.rejected = false;
+		state.generation += 1;
+		state.token = token;
+		Ok(Credentials::new(&self.username, &state.token.access_token))
+	}
+
+	/// Marks the access token as rejected so the next call to [`TwitchIdentity::credentials`] refreshes it.
+	///
+	/// Returns `false` if the rejected token was itself refreshed in response to the previous rejection, since
+	/// refreshing again is unlikely to help (e.g. the account lacks the `chat:read` scope).
+	fn invalidate(&self) -> bool {
+		let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+		if !state.rejected && state.rejected_generation.is_some_and(|generation| generation + 1 == state.generation) {
+			return false;
+		}
+		state.rejected = true;
+		state.rejected_generation = Some(state.generation);
+		true
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{
+		convert::Infallible,
+		sync::atomic::{AtomicUsize, Ordering},
+		time::Duration
+	};
+
+	use bytes::Bytes;
+
+	use super::{RefreshingIdentity, TwitchIdentity, UserToken};
+	use crate::executor::{RequestExecutor, Response};
+
+	#[derive(Debug, Default)]
+	struct TokenEndpoint(AtomicUsize);
+
+	#[derive(Debug)]
+	struct TokenResponse(Option<Bytes>);
+
+	impl Response for TokenResponse {
+		type Error = Infallible;
+
+		fn status_code(&self) -> u16 {
+			200
+		}
+
+		async fn recv_chunk(&mut self) -> Result<Option<Bytes>, Self::Error> {
+			Ok(self.0.take())
+		}
+	}
+
+	impl RequestExecutor for TokenEndpoint {
+		type Response = TokenResponse;
+		type Error = Infallible;
+
+		async fn make_request(&self, _: http::Request<Bytes>) -> Result<Self::Response, Self::Error> {
+			let n = self.0.fetch_add(1, Ordering::SeqCst) + 1;
+			Ok(TokenResponse(Some(Bytes::from(format!(r#"{{"access_token":"access{n}","refresh_token":"refresh{n}","expires_in":14400}}"#)))))
+		}
+
+		async fn sleep(dur: Duration) {
+			tokio::time::sleep(dur).await;
+		}
+	}
+
+	#[tokio::test]
+	async fn stops_refreshing_when_refreshed_token_is_rejected() {
+		let identity = RefreshingIdentity::new(
+			TokenEndpoint::default(),
+			"yukifan4",
+			"client",
+			UserToken {
+				access_token: "access0".to_owned(),
+				refresh_token: "refresh0".to_owned()
+			}
+		);
+		assert_eq!(identity.credentials().await.unwrap().token.as_deref(), Some("access0"));
+
+		assert!(identity.invalidate());
+		// Repeated rejections of the same token before it is refreshed still expect a refresh.
+		assert!(identity.invalidate());
+		assert_eq!(identity.credentials().await.unwrap().token.as_deref(), Some("access1"));
+
+		assert!(!identity.invalidate());
+		assert_eq!(identity.credentials().await.unwrap().token.as_deref(), Some("access1"));
+	}
+}
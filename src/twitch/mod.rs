@@ -13,34 +13,109 @@
 // limitations under the License.
 
 use std::{
+	collections::{HashMap, VecDeque},
+	fmt,
+	future::Future,
 	pin::Pin,
-	task::{Context, Poll}
+	sync::{
+		Arc,
+		atomic::{AtomicUsize, Ordering}
+	},
+	task::{Context, Poll, Waker}
 };
 
+use futures_channel::mpsc;
 use futures_util::{Stream, StreamExt};
-use irc::{
-	client::{Client, ClientStream, prelude::Config},
-	proto::Capability
-};
+use irc::proto::{Command, Response as IrcResponse};
+use tokio::time::{Instant, Sleep};
 
+#[cfg(feature = "twitch-emotes")]
+pub mod emotes;
+#[cfg(feature = "twitch-eventsub")]
+pub mod eventsub;
+#[cfg(feature = "twitch-helix")]
+pub mod helix;
 pub mod identity;
-pub use self::identity::{Anonymous, Authenticated, TwitchIdentity};
+pub use self::identity::{Anonymous, Authenticated, Credentials, IntoTwitchIdentity, TwitchIdentity};
+#[cfg(feature = "twitch-helix")]
+pub use self::identity::{RefreshingIdentity, UserToken};
+mod builder;
+pub use self::builder::{Capabilities, ChatBuilder};
+mod chatters;
+pub use self::chatters::Chatters;
+mod cheer;
+pub use self::cheer::Cheermotes;
+mod error;
+pub use self::error::Error;
 mod event;
-pub use self::event::{ChatEvent, MessageSegment, User, UserRole};
+pub use self::event::{
+	AnnouncementColor, Badge, Channel, ChatEvent, FollowersOnly, GiftRecipient, MessageEffect, MessageSegment, NoticeKind, ReplyInfo, RoomState,
+	RoomStateUpdate, SharedChatSource, SubTier, User, UserFlags, UserRole, UserState
+};
+mod parse;
+pub use self::parse::{ParseError, parse_line, parse_line_with};
+mod ratelimit;
+use self::ratelimit::RateLimiter;
+pub use self::ratelimit::{Limit, RateLimits};
+mod reconnect;
+pub use self::reconnect::{DisconnectReason, ReconnectPolicy};
+mod sender;
+pub use self::sender::{ChatSender, SendError, SendRejection};
+use self::sender::{PendingSends, QueuedMessage};
+mod thread;
+pub use self::thread::{CachedMessage, ReplyParent, ThreadCache};
+mod transport;
+pub use self::transport::Transport;
+use self::transport::{ConnectOptions, Established, IrcSender, IrcStream, connect};
+#[cfg(any(feature = "twitch-helix", feature = "twitch-emotes"))]
+pub use crate::executor::{RequestExecutor, Response};
+
+type ConnectFuture = Pin<Box<dyn Future<Output = Result<Established, Error>> + Send>>;
+
+enum Connection {
+	Connected { stream: IrcStream, sender: IrcSender },
+	Reconnecting { attempt: u32, future: ConnectFuture },
+	Closed
+}
 
-const TWITCH_SECURE_IRC: (&str, u16) = ("irc.chat.twitch.tv", 6697);
-const TWITCH_CAPABILITY_TAGS: Capability = Capability::Custom("twitch.tv/tags");
-const TWITCH_CAPABILITY_MEMBERSHIP: Capability = Capability::Custom("twitch.tv/membership");
-const TWITCH_CAPABILITY_COMMANDS: Capability = Capability::Custom("twitch.tv/commands");
+impl fmt::Debug for Connection {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Connected { stream, .. } => f.debug_struct("Connected").field("stream", stream).finish_non_exhaustive(),
+			Self::Reconnecting { attempt, .. } => f.debug_struct("Reconnecting").field("attempt", attempt).finish_non_exhaustive(),
+			Self::Closed => f.write_str("Closed")
+		}
+	}
+}
 
-/// A connection to a Twitch IRC channel.
+/// A connection to one or more Twitch IRC channels.
 ///
 /// In order for the connection to stay alive, the IRC client must be able to receive and respond to ping messages, thus
 /// you must poll the stream for as long as you wish the client to stay alive. If that isn't possible, start a dedicated
 /// thread for the client and send chat events back to your application over an `mpsc` or other channel.
 #[derive(Debug)]
 pub struct Chat {
-	stream: ClientStream
+	options: ConnectOptions,
+	connection: Connection,
+	/// Whether the server has welcomed us on the current connection; nothing is sent until it has.
+	registered: bool,
+	outgoing: mpsc::UnboundedReceiver<QueuedMessage>,
+	sender: ChatSender,
+	pending_sends: PendingSends,
+	queue: VecDeque<QueuedMessage>,
+	queue_depth: Arc<AtomicUsize>,
+	join_queue: VecDeque<String>,
+	limiter: RateLimiter,
+	timer: Option<Pin<Box<Sleep>>>,
+	waker: Option<Waker>,
+	channels: HashMap<String, RoomState>,
+	user_states: HashMap<String, UserState>,
+	global_user_state: Option<(u64, UserState)>,
+	cheermotes: Cheermotes,
+	reconnect: Option<ReconnectPolicy>,
+	/// The number of connection attempts made since the server last welcomed us. A connection that is accepted but
+	/// closed before `RPL_WELCOME` counts as failed, so that it's still subject to backoff.
+	failed_attempts: u32
 }
 
 impl Chat {
@@ -55,39 +130,440 @@ impl Chat {
 	/// # Ok(())
 	/// # }
 	/// ```
-	pub async fn new(channel: impl AsRef<str>, auth: impl TwitchIdentity) -> irc::error::Result<Self> {
-		let (username, password) = auth.as_identity();
-		let mut client = Client::from_config(Config {
-			server: Some(TWITCH_SECURE_IRC.0.to_string()),
-			port: Some(TWITCH_SECURE_IRC.1),
-			nickname: Some(username.to_string()),
-			password: password.map(|c| format!("oauth:{c}")),
-			channels: vec![format!("#{}", channel.as_ref())],
-			..Default::default()
-		})
-		.await?;
-		client.send_cap_req(&[TWITCH_CAPABILITY_COMMANDS, TWITCH_CAPABILITY_MEMBERSHIP, TWITCH_CAPABILITY_TAGS])?;
-		client.identify()?;
-		Ok(Self { stream: client.stream()? })
+	///
+	/// To connect to a different server or customize the connection, use [`Chat::builder`].
+	pub async fn new(channel: impl AsRef<str>, auth: impl IntoTwitchIdentity) -> Result<Self, Error> {
+		ChatBuilder::new().connect(channel, auth).await
+	}
+
+	/// Returns a [`ChatBuilder`] to configure the connection.
+	pub fn builder() -> ChatBuilder {
+		ChatBuilder::new()
+	}
+
+	pub(crate) fn from_connection(connection: Established, options: ConnectOptions, channel: &str) -> Self {
+		let Established { stream, sender, authenticated } = connection;
+		let channel = normalize_channel(channel);
+		let (outgoing_tx, outgoing) = mpsc::unbounded();
+		let queue_depth = Arc::new(AtomicUsize::new(0));
+		Self {
+			options,
+			connection: Connection::Connected { stream, sender },
+			registered: false,
+			outgoing,
+			sender: ChatSender::new(outgoing_tx, channel.clone(), authenticated, Arc::clone(&queue_depth)),
+			pending_sends: PendingSends::default(),
+			queue: VecDeque::new(),
+			queue_depth,
+			join_queue: VecDeque::from([channel.clone()]),
+			limiter: RateLimiter::new(RateLimits::default()),
+			timer: None,
+			waker: None,
+			channels: HashMap::from([(channel, RoomState::default())]),
+			user_states: HashMap::new(),
+			global_user_state: None,
+			cheermotes: Cheermotes::default(),
+			reconnect: None,
+			failed_attempts: 0
+		}
+	}
+
+	/// Enables automatic reconnection using the given [`ReconnectPolicy`].
+	///
+	/// When the connection drops, or when Twitch sends a `RECONNECT` command ahead of server maintenance, the stream
+	/// yields [`ChatEvent::Disconnected`] and transparently establishes a new connection, re-joining all channels
+	/// joined at the time. Once connected, [`ChatEvent::Reconnected`] is yielded. Messages sent through a
+	/// [`ChatSender`] while disconnected are queued and sent after reconnecting.
+	///
+	/// ```no_run
+	/// use brainrot::twitch::{Anonymous, Chat, ReconnectPolicy};
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> anyhow::Result<()> {
+	/// let mut client = Chat::new("miyukiwei", Anonymous).await?.with_reconnect(ReconnectPolicy::default());
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn with_reconnect(mut self, policy: ReconnectPolicy) -> Self {
+		self.reconnect = Some(policy);
+		self
+	}
+
+	/// Sets the cheermote prefixes used to recognize [`MessageSegment::Cheer`]s in [`ChatEvent::SendBits`] messages.
+	/// Only [global cheermotes](Cheermotes::global) are recognized by default.
+	///
+	/// The set applies to all joined channels; to recognize the custom cheermotes of multiple channels,
+	/// [extend](Cheermotes::extend) the set with each channel's prefixes.
+	pub fn with_cheermotes(mut self, cheermotes: Cheermotes) -> Self {
+		self.cheermotes = cheermotes;
+		self
+	}
+
+	/// Sets the rate limits used to pace outgoing messages and `JOIN`s. [`RateLimits::normal`] is used by default.
+	pub fn with_rate_limits(mut self, limits: RateLimits) -> Self {
+		self.limiter.set_limits(limits);
+		self
+	}
+
+	/// Mutable access to the cheermote set, e.g. to add a channel's custom cheermotes after [joining](Chat::join) it.
+	pub fn cheermotes_mut(&mut self) -> &mut Cheermotes {
+		&mut self.cheermotes
+	}
+
+	/// Joins another channel on this connection. Events from all joined channels are received through the same stream;
+	/// use [`ChatEvent::channel`] to tell them apart.
+	///
+	/// ```no_run
+	/// use brainrot::twitch::{Anonymous, Chat};
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> anyhow::Result<()> {
+	/// let mut client = Chat::new("miyukiwei", Anonymous).await?;
+	/// client.join("sinder");
+	/// # Ok(())
+	/// # }
+	/// ```
+	///
+	/// `JOIN`s are subject to [rate limits](RateLimits), so the channel may not be joined immediately when joining many
+	/// channels at once.
+	pub fn join(&mut self, channel: impl AsRef<str>) {
+		let channel = normalize_channel(channel.as_ref());
+		if self.channels.contains_key(&channel) {
+			return;
+		}
+
+		self.channels.insert(channel.clone(), RoomState::default());
+		self.join_queue.push_back(channel);
+		// The stream may be waiting on the connection; wake it so the `JOIN` is sent right away.
+		if let Some(waker) = &self.waker {
+			waker.wake_by_ref();
+		}
+	}
+
+	/// Leaves a channel previously joined via [`Chat::new`] or [`Chat::join`]. Does nothing if the channel isn't
+	/// joined.
+	pub fn part(&mut self, channel: impl AsRef<str>) {
+		let channel = normalize_channel(channel.as_ref());
+		if self.channels.remove(&channel).is_none() {
+			return;
+		}
+		self.user_states.remove(&channel);
+		self.limiter.forget_channel(&channel);
+
+		// If we haven't gotten around to joining the channel yet (including while (re)connecting), there's nothing to
+		// part.
+		if let Some(i) = self.join_queue.iter().position(|queued| *queued == channel) {
+			self.join_queue.remove(i);
+			return;
+		}
+
+		// If the connection is lost before the `PART` is sent, the channel simply won't be rejoined.
+		match &self.connection {
+			Connection::Connected { sender, .. } if self.registered => {
+				let _ = sender.send_part(format!("#{channel}"));
+			}
+			_ => {}
+		}
+	}
+
+	/// Returns an iterator over the names of all currently joined channels.
+	pub fn channels(&self) -> impl Iterator<Item = &str> + '_ {
+		self.channels.keys().map(String::as_str)
+	}
+
+	/// Returns a [`ChatSender`] that can be used to send messages to the channel passed to [`Chat::new`] from any task.
+	/// Use [`ChatSender::with_channel`] to send messages to other joined channels.
+	///
+	/// Sending messages requires an [`Authenticated`] identity; senders obtained from an [`Anonymous`] connection
+	/// will always return [`SendError::Anonymous`].
+	pub fn sender(&self) -> ChatSender {
+		self.sender.clone()
+	}
+
+	/// The number of messages waiting to be sent due to [rate limits](RateLimits).
+	pub fn queue_depth(&self) -> usize {
+		self.queue_depth.load(Ordering::Relaxed)
+	}
+
+	/// The number of channels waiting to be joined due to [rate limits](RateLimits).
+	pub fn queued_joins(&self) -> usize {
+		self.join_queue.len()
+	}
+
+	/// Returns a joined channel's current chat settings, merged from all [`ChatEvent::RoomState`] events received so
+	/// far.
+	pub fn room_state(&self, channel: impl AsRef<str>) -> Option<&RoomState> {
+		self.channels.get(normalize_channel(channel.as_ref()).as_str())
+	}
+
+	/// Returns the connected account's state in a joined channel, from the latest [`ChatEvent::UserState`]. This can
+	/// be used to check whether the account [is a moderator](UserState::is_moderator) before attempting to moderate.
+	///
+	/// Returns `None` until Twitch sends the state after joining, which it only does for [`Authenticated`] identities.
+	pub fn user_state(&self, channel: impl AsRef<str>) -> Option<&UserState> {
+		self.user_states.get(normalize_channel(channel.as_ref()).as_str())
+	}
+
+	/// Returns the connected account's global state, from the latest [`ChatEvent::GlobalUserState`]. Only available
+	/// for [`Authenticated`] identities.
+	pub fn global_user_state(&self) -> Option<&UserState> {
+		self.global_user_state.as_ref().map(|(_, state)| state)
+	}
+
+	/// Returns the connected account's user ID, as reported by [`ChatEvent::GlobalUserState`]. Only available for
+	/// [`Authenticated`] identities.
+	pub fn user_id(&self) -> Option<u64> {
+		self.global_user_state.as_ref().map(|(user_id, _)| *user_id)
+	}
+}
+
+fn is_authentication_failure(notice: &str) -> bool {
+	matches!(notice, "Login authentication failed" | "Improperly formatted auth")
+}
+
+fn normalize_channel(channel: &str) -> String {
+	channel.trim_start_matches('#').to_ascii_lowercase()
+}
+
+impl Chat {
+	/// Starts a new connection attempt, after the policy's backoff delay unless `immediate`.
+	fn start_reconnect(&mut self, attempt: u32, immediate: bool) {
+		let Some(policy) = &self.reconnect else {
+			self.connection = Connection::Closed;
+			return;
+		};
+
+		// Sends awaiting acknowledgement will never receive one, so fail them with `SendError::Disconnected`.
+		self.pending_sends.clear();
+
+		// Re-join all channels once the new connection is registered.
+		self.registered = false;
+		self.join_queue = self.channels.keys().cloned().collect();
+
+		let delay = if immediate { None } else { Some(policy.delay(self.failed_attempts)) };
+		self.failed_attempts += 1;
+		let options = self.options.clone();
+		self.connection = Connection::Reconnecting {
+			attempt,
+			future: Box::pin(async move {
+				if let Some(delay) = delay {
+					tokio::time::sleep(delay).await;
+				}
+				connect(options).await
+			})
+		};
+	}
+
+	fn disconnect(&mut self, reason: DisconnectReason) -> Poll<Option<<Self as Stream>::Item>> {
+		let should_retry = self.reconnect.as_ref().is_some_and(|policy| policy.should_retry(self.failed_attempts));
+		if !should_retry {
+			self.connection = Connection::Closed;
+			return match reason {
+				DisconnectReason::Error(e) => Poll::Ready(Some(Err(e))),
+				_ => Poll::Ready(None)
+			};
+		}
+
+		// Twitch asks us to reconnect ahead of maintenance, so the first reconnect after a healthy connection is made
+		// right away. Anything else is backed off, in case the server is refusing us.
+		let immediate = matches!(reason, DisconnectReason::ServerRequested) && self.failed_attempts == 0;
+		self.start_reconnect(0, immediate);
+		Poll::Ready(Some(Ok(ChatEvent::Disconnected { reason })))
+	}
+
+	/// Sends queued messages and `JOIN`s as the rate limits allow, failing messages whose deadline has passed, and
+	/// arms a timer to wake the stream when the next queued command can be sent.
+	fn poll_queue(&mut self, cx: &mut Context<'_>) {
+		match &mut self.waker {
+			Some(waker) => waker.clone_from(cx.waker()),
+			None => self.waker = Some(cx.waker().clone())
+		}
+
+		while let Poll::Ready(Some(message)) = self.outgoing.poll_next_unpin(cx) {
+			self.queue.push_back(message);
+		}
+
+		let now = Instant::now();
+		let sender = match &self.connection {
+			Connection::Connected { sender, .. } if self.registered => Some(sender.clone()),
+			_ => None
+		};
+		let mut wake_at: Option<Instant> = None;
+
+		// Channels must be joined before messages can be sent to them.
+		if let Some(sender) = &sender {
+			while !self.join_queue.is_empty() {
+				if let Some(ready_at) = self.limiter.join_ready_at(now) {
+					wake_at = Some(ready_at);
+					break;
+				}
+				let channel = self.join_queue.pop_front().expect("queue is not empty");
+				self.limiter.record_join(now);
+				let _ = sender.send_join(format!("#{channel}"));
+			}
+		}
+
+		let mut i = 0;
+		while i < self.queue.len() {
+			let queued = &self.queue[i];
+			// `None` if the message can't be sent at all yet, i.e. we aren't connected or its channel hasn't been joined.
+			let ready_at = match &sender {
+				Some(_) if !self.join_queue.contains(&queued.channel) => Some(self.limiter.message_ready_at(&queued.channel, now)),
+				_ => None
+			};
+			let expired = queued
+				.deadline
+				.is_some_and(|deadline| deadline <= now || matches!(ready_at, Some(Some(ready_at)) if deadline < ready_at));
+			if expired || queued.tx.is_canceled() {
+				let queued = self.queue.remove(i).expect("index is in bounds");
+				self.queue_depth.fetch_sub(1, Ordering::Relaxed);
+				let _ = queued.tx.send(Err(SendError::DeadlineExceeded));
+				continue;
+			}
+
+			match (ready_at, &sender) {
+				(Some(None), Some(sender)) => {
+					let queued = self.queue.remove(i).expect("index is in bounds");
+					self.queue_depth.fetch_sub(1, Ordering::Relaxed);
+					self.limiter.record_message(now);
+					self.pending_sends.push(&queued.channel, queued.tx, now);
+					// If this fails, the connection is gone and the stream will tell us shortly.
+					let _ = sender.send(queued.message);
+				}
+				_ => {
+					wake_at = [wake_at, ready_at.flatten(), queued.deadline].into_iter().flatten().min();
+					i += 1;
+				}
+			}
+		}
+
+		if let Some(expires_at) = self.pending_sends.expire(now) {
+			wake_at = Some(wake_at.map_or(expires_at, |wake_at| wake_at.min(expires_at)));
+		}
+
+		self.timer = wake_at.map(|wake_at| match self.timer.take() {
+			Some(mut timer) => {
+				timer.as_mut().reset(wake_at);
+				timer
+			}
+			None => Box::pin(tokio::time::sleep_until(wake_at))
+		});
+		if let Some(timer) = &mut self.timer
+			&& timer.as_mut().poll(cx).is_ready()
+		{
+			cx.waker().wake_by_ref();
+		}
+	}
+
+	fn handle_event(&mut self, mut ev: ChatEvent) -> ChatEvent {
+		if let ChatEvent::RoomState { channel, update } = &ev
+			&& let Some(state) = self.channels.get_mut(&channel.name)
+		{
+			state.apply(channel.room_id, update);
+		}
+		match &ev {
+			ChatEvent::UserState { channel, state, .. } if self.channels.contains_key(&channel.name) => {
+				self.limiter.set_privileged(&channel.name, state.is_moderator());
+				self.user_states.insert(channel.name.clone(), state.clone());
+			}
+			ChatEvent::GlobalUserState { user_id, state } => {
+				self.global_user_state = Some((*user_id, state.clone()));
+			}
+			_ => {}
+		}
+		if let Some(channel) = ev.channel_mut()
+			&& channel.room_id.is_none()
+		{
+			channel.room_id = self.channels.get(&channel.name).and_then(|state| state.room_id);
+		}
+		ev
 	}
 }
 
 impl Stream for Chat {
-	type Item = irc::error::Result<ChatEvent>;
+	type Item = Result<ChatEvent, Error>;
 
 	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-		let next = self.stream.poll_next_unpin(cx);
-		match next {
-			Poll::Ready(Some(Ok(r))) => match self::event::to_chat_event(r) {
-				Some(ev) => Poll::Ready(Some(Ok(ev))),
-				None => {
-					cx.waker().wake_by_ref();
-					Poll::Pending
-				}
-			},
-			Poll::Ready(Some(Err(e))) => Poll::Ready(Some(Err(e))),
-			Poll::Ready(None) => Poll::Ready(None),
-			Poll::Pending => Poll::Pending
+		let this = &mut *self;
+		this.poll_queue(cx);
+		// Whether we've consumed messages that don't produce an event, like `PING`s & numeric replies. These are drained
+		// in a loop rather than by waking ourselves for each one, but may still change what the queue can send (e.g.
+		// `RPL_WELCOME` allows queued `JOIN`s to go out), so the queue is polled again before returning `Pending`.
+		let mut filtered = false;
+		loop {
+			return match &mut this.connection {
+				Connection::Connected { stream, .. } => match stream.poll_next_unpin(cx) {
+					Poll::Ready(Some(Ok(r))) => {
+						if matches!(&r.command, Command::Raw(command, _) if command == "RECONNECT") {
+							return this.disconnect(DisconnectReason::ServerRequested);
+						}
+
+						if let Command::NOTICE(_, text) = &r.command
+							&& is_authentication_failure(text)
+						{
+							// Let the identity refresh its token whether or not we reconnect, so a later `Chat` can use it.
+							let refreshed = this.options.identity.invalidate();
+							if refreshed && this.reconnect.is_some() {
+								return this.disconnect(DisconnectReason::AuthenticationFailed);
+							}
+							this.connection = Connection::Closed;
+							return Poll::Ready(Some(Err(Error::Authentication { message: text.clone() })));
+						}
+
+						if let Command::Response(IrcResponse::RPL_WELCOME, _) = &r.command {
+							this.registered = true;
+							this.failed_attempts = 0;
+						}
+						// Notices in response to our own messages are reported through the `ChatSender`; any others about being
+						// unable to participate in a channel are errors.
+						if !this.pending_sends.handle_message(&r)
+							&& let Some(error) = Error::from_notice(&r)
+						{
+							if let Error::ChannelSuspended { channel, .. } = &error {
+								this.channels.remove(channel);
+								this.user_states.remove(channel);
+								this.limiter.forget_channel(channel);
+							}
+							return Poll::Ready(Some(Err(error)));
+						}
+						match self::event::to_chat_event(r, &this.cheermotes) {
+							Some(ev) => Poll::Ready(Some(Ok(this.handle_event(ev)))),
+							None => {
+								filtered = true;
+								continue;
+							}
+						}
+					}
+					Poll::Ready(Some(Err(e))) => this.disconnect(DisconnectReason::Error(e)),
+					Poll::Ready(None) => this.disconnect(DisconnectReason::Closed),
+					Poll::Pending => {
+						if filtered {
+							this.poll_queue(cx);
+						}
+						Poll::Pending
+					}
+				},
+				Connection::Reconnecting { attempt, future } => match future.as_mut().poll(cx) {
+					Poll::Ready(Ok(Established { stream, sender, .. })) => {
+						let attempts = *attempt + 1;
+						this.connection = Connection::Connected { stream, sender };
+						Poll::Ready(Some(Ok(ChatEvent::Reconnected { attempts })))
+					}
+					Poll::Ready(Err(e)) => {
+						let attempt = *attempt + 1;
+						if this.reconnect.as_ref().is_some_and(|policy| policy.should_retry(this.failed_attempts)) {
+							tracing::warn!("reconnection attempt {attempt} failed: {e}");
+							this.start_reconnect(attempt, false);
+							continue;
+						} else {
+							this.connection = Connection::Closed;
+							Poll::Ready(Some(Err(e)))
+						}
+					}
+					Poll::Pending => Poll::Pending
+				},
+				Connection::Closed => Poll::Ready(None)
+			};
 		}
 	}
 }
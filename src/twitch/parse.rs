@@ -0,0 +1,89 @@
+// Copyright 2026 pyke.io
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{error::Error as StdError, fmt};
+
+use irc::proto::{Message, error::ProtocolError};
+
+use super::{ChatEvent, Cheermotes, event::to_chat_event};
+
+/// Parses a single raw IRC line, as sent by Twitch, into a [`ChatEvent`].
+///
+/// This performs no I/O, so it can be used to reprocess logged chat or to build a custom transport. A trailing `\r\n`
+/// is allowed but not required.
+///
+/// Returns `Ok(None)` for lines that are valid IRC but don't correspond to a chat event (e.g. `PING`), or that lack
+/// the tags required to build one (i.e. lines received without the `twitch.tv/tags` capability).
+///
+/// ```
+/// use brainrot::twitch::{ChatEvent, parse_line};
+///
+/// let event = parse_line("@room-id=1;tmi-sent-ts=1700000000000 :tmi.twitch.tv CLEARCHAT #miyukiwei")?;
+/// assert!(matches!(event, Some(ChatEvent::ChatCleared { .. })));
+/// # Ok::<_, brainrot::twitch::ParseError>(())
+/// ```
+pub fn parse_line(line: &str) -> Result<Option<ChatEvent>, ParseError> {
+	parse_line_with(line, &Cheermotes::default())
+}
+
+/// Like [`parse_line`], but recognizes cheers using the given set of [`Cheermotes`] instead of only the global ones.
+pub fn parse_line_with(line: &str, cheermotes: &Cheermotes) -> Result<Option<ChatEvent>, ParseError> {
+	let message: Message = line.trim_end_matches(['\r', '\n']).parse().map_err(ParseError::Malformed)?;
+	Ok(to_chat_event(message, cheermotes))
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+	/// The line is not a valid IRC message.
+	Malformed(ProtocolError)
+}
+
+impl fmt::Display for ParseError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Malformed(e) => f.write_fmt(format_args!("malformed IRC message: {e}"))
+		}
+	}
+}
+
+impl StdError for ParseError {
+	fn source(&self) -> Option<&(dyn StdError + 'static)> {
+		match self {
+			Self::Malformed(e) => Some(e)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::error::Error as _;
+
+	use super::parse_line;
+	use crate::twitch::ChatEvent;
+
+	#[test]
+	fn lines() {
+		let event = parse_line("@emote-only=1;room-id=1 :tmi.twitch.tv ROOMSTATE #miyukiwei\r\n").unwrap();
+		assert!(matches!(event, Some(ChatEvent::RoomState { update, .. }) if update.emote_only == Some(true)));
+		// Lines that don't produce an event aren't errors.
+		assert!(parse_line("PING :tmi.twitch.tv").unwrap().is_none());
+		assert!(parse_line("").is_err());
+	}
+
+	#[test]
+	fn malformed_lines() {
+		let e = parse_line("").unwrap_err();
+		assert!(e.source().is_some());
+	}
+}
@@ -0,0 +1,295 @@
+// Copyright 2026 pyke.io
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+	collections::{HashSet, VecDeque},
+	time::Duration
+};
+
+use tokio::time::Instant;
+
+/// Allows `count` actions in any sliding window of `window`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Limit {
+	pub count: u32,
+	pub window: Duration
+}
+
+impl Limit {
+	pub const fn new(count: u32, window: Duration) -> Self {
+		Self { count, window }
+	}
+}
+
+/// The [rate limits](https://dev.twitch.tv/docs/chat/#rate-limits) [`Chat`](super::Chat) enforces on outgoing
+/// messages and `JOIN`s.
+///
+/// Twitch silently drops messages sent over the limit, and disconnects clients that exceed the `JOIN` limit, so
+/// outgoing commands are queued until they can be sent without going over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RateLimits {
+	/// The limit for messages sent to channels in which the account is not the broadcaster or a moderator.
+	pub messages: Limit,
+	/// The limit for messages sent to channels in which the account is the broadcaster or a moderator. Messages sent
+	/// to these channels also count towards [`RateLimits::messages`], but aren't limited by it.
+	pub privileged_messages: Limit,
+	/// The limit for `JOIN`s.
+	pub joins: Limit
+}
+
+impl RateLimits {
+	/// Limits for a regular account.
+	pub const fn normal() -> Self {
+		Self {
+			messages: Limit::new(20, Duration::from_secs(30)),
+			privileged_messages: Limit::new(100, Duration::from_secs(30)),
+			joins: Limit::new(20, Duration::from_secs(10))
+		}
+	}
+
+	/// Limits for an account Twitch has granted verified bot status.
+	pub const fn verified_bot() -> Self {
+		Self {
+			messages: Limit::new(7500, Duration::from_secs(30)),
+			privileged_messages: Limit::new(7500, Duration::from_secs(30)),
+			joins: Limit::new(2000, Duration::from_secs(10))
+		}
+	}
+}
+
+impl Default for RateLimits {
+	fn default() -> Self {
+		Self::normal()
+	}
+}
+
+/// A sliding window log of when actions were taken.
+#[derive(Debug, Default)]
+struct Log(VecDeque<Instant>);
+
+impl Log {
+	fn prune(&mut self, now: Instant, window: Duration) {
+		while self.0.front().is_some_and(|&t| now.duration_since(t) >= window) {
+			self.0.pop_front();
+		}
+	}
+
+	/// Returns when another action will be allowed under `limit`, or `None` if it is allowed now.
+	fn ready_at(&self, now: Instant, limit: Limit) -> Option<Instant> {
+		let count = limit.count.max(1) as usize;
+		let in_window = self.0.iter().rev().take_while(|&&t| now.duration_since(t) < limit.window).count();
+		if in_window < count {
+			return None;
+		}
+		// The action is allowed once the oldest of the last `count` actions leaves the window.
+		let oldest = self.0[self.0.len() - count];
+		Some(oldest + limit.window)
+	}
+}
+
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+	limits: RateLimits,
+	messages: Log,
+	joins: Log,
+	/// Channels in which the account is the broadcaster or a moderator, as reported by `USERSTATE`.
+	privileged: HashSet<String>
+}
+
+impl RateLimiter {
+	pub(crate) fn new(limits: RateLimits) -> Self {
+		Self {
+			limits,
+			messages: Log::default(),
+			joins: Log::default(),
+			privileged: HashSet::new()
+		}
+	}
+
+	pub(crate) fn set_limits(&mut self, limits: RateLimits) {
+		self.limits = limits;
+	}
+
+	fn prune(&mut self, now: Instant) {
+		self.messages
+			.prune(now, self.limits.messages.window.max(self.limits.privileged_messages.window));
+		self.joins.prune(now, self.limits.joins.window);
+	}
+
+	/// Returns when a message to `channel` will be allowed, or `None` if it can be sent now.
+	pub(crate) fn message_ready_at(&mut self, channel: &str, now: Instant) -> Option<Instant> {
+		self.prune(now);
+		let limit = if self.privileged.contains(channel) {
+			self.limits.privileged_messages
+		} else {
+			self.limits.messages
+		};
+		self.messages.ready_at(now, limit)
+	}
+
+	/// Returns when a `JOIN` will be allowed, or `None` if one can be sent now.
+	pub(crate) fn join_ready_at(&mut self, now: Instant) -> Option<Instant> {
+		self.prune(now);
+		self.joins.ready_at(now, self.limits.joins)
+	}
+
+	pub(crate) fn record_message(&mut self, now: Instant) {
+		self.messages.0.push_back(now);
+	}
+
+	pub(crate) fn record_join(&mut self, now: Instant) {
+		self.joins.0.push_back(now);
+	}
+
+	pub(crate) fn forget_channel(&mut self, channel: &str) {
+		self.privileged.remove(channel);
+	}
+
+	/// Sets whether the account is the broadcaster or a moderator in `channel`, which raises its message limit there.
+	pub(crate) fn set_privileged(&mut self, channel: &str, privileged: bool) {
+		if privileged {
+			self.privileged.insert(channel.to_owned());
+		} else {
+			self.privileged.remove(channel);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{net::TcpListener, thread, time::Duration};
+
+	use futures_util::StreamExt;
+	use tokio::time::Instant;
+
+	use super::{Limit, RateLimiter, RateLimits};
+	use crate::twitch::{Authenticated, Chat, Cheermotes, SendError, event::to_chat_event};
+
+	/// Connects to a server that accepts the connection but never welcomes the client, so nothing is ever sent.
+	async fn unwelcome_chat() -> Chat {
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		let port = listener.local_addr().unwrap().port();
+		thread::spawn(move || {
+			let sockets: Vec<_> = listener.incoming().collect();
+			drop(sockets);
+		});
+		Chat::builder()
+			.server("127.0.0.1")
+			.port(port)
+			.tls(false)
+			.connect("miyukiwei", Authenticated("yukifan4", "yfvzjqb705z12hrhy1zkwa9xt7v662"))
+			.await
+			.unwrap()
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn sliding_window() {
+		let mut limiter = RateLimiter::new(RateLimits {
+			messages: Limit::new(2, Duration::from_secs(10)),
+			..RateLimits::normal()
+		});
+		let start = Instant::now();
+		assert_eq!(limiter.message_ready_at("miyukiwei", start), None);
+		limiter.record_message(start);
+		tokio::time::advance(Duration::from_secs(4)).await;
+		limiter.record_message(Instant::now());
+		assert_eq!(limiter.message_ready_at("miyukiwei", Instant::now()), Some(start + Duration::from_secs(10)));
+
+		// Once the first message leaves the window, another can be sent, but then we have to wait for the second.
+		tokio::time::advance(Duration::from_secs(6)).await;
+		assert_eq!(limiter.message_ready_at("miyukiwei", Instant::now()), None);
+		limiter.record_message(Instant::now());
+		assert_eq!(limiter.message_ready_at("miyukiwei", Instant::now()), Some(start + Duration::from_secs(14)));
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn joins() {
+		let mut limiter = RateLimiter::new(RateLimits {
+			joins: Limit::new(2, Duration::from_secs(10)),
+			..RateLimits::normal()
+		});
+		let start = Instant::now();
+		limiter.record_join(start);
+		limiter.record_join(start);
+		assert_eq!(limiter.join_ready_at(start), Some(start + Duration::from_secs(10)));
+		// Joins and messages are limited separately.
+		assert_eq!(limiter.message_ready_at("miyukiwei", start), None);
+
+		tokio::time::advance(Duration::from_secs(10)).await;
+		assert_eq!(limiter.join_ready_at(Instant::now()), None);
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn verified_bot() {
+		let mut limiter = RateLimiter::new(RateLimits::normal());
+		let now = Instant::now();
+		for _ in 0..20 {
+			limiter.record_message(now);
+		}
+		assert_eq!(limiter.message_ready_at("miyukiwei", now), Some(now + Duration::from_secs(30)));
+
+		limiter.set_limits(RateLimits::verified_bot());
+		assert_eq!(limiter.message_ready_at("miyukiwei", now), None);
+	}
+
+	#[tokio::test]
+	async fn moderator_limits_follow_user_state() {
+		let mut chat = unwelcome_chat().await;
+		tokio::time::pause();
+		let now = Instant::now();
+		for _ in 0..20 {
+			chat.limiter.record_message(now);
+		}
+		let mut user_state = |line: &str| {
+			let event = to_chat_event(line.parse().unwrap(), &Cheermotes::default()).unwrap();
+			chat.handle_event(event);
+			chat.limiter.message_ready_at("miyukiwei", Instant::now())
+		};
+
+		let moderator =
+			"@badge-info=;badges=moderator/1;color=;display-name=yukifan4;emote-sets=0;mod=1;subscriber=0;user-type=mod :tmi.twitch.tv USERSTATE #miyukiwei";
+		assert_eq!(user_state(moderator), None);
+		let demoted = "@badge-info=;badges=;color=;display-name=yukifan4;emote-sets=0;mod=0;subscriber=0;user-type= :tmi.twitch.tv USERSTATE #miyukiwei";
+		assert_eq!(user_state(demoted), Some(now + Duration::from_secs(30)));
+	}
+
+	#[tokio::test]
+	async fn deadlines_expire() {
+		let mut chat = unwelcome_chat().await;
+		tokio::time::pause();
+		let sender = chat.sender();
+		let start = Instant::now();
+		let waiting = tokio::spawn({
+			let sender = sender.clone();
+			async move { sender.say("hi").await }
+		});
+		let expiring = tokio::spawn({
+			let sender = sender.with_deadline(Duration::from_secs(5));
+			async move { sender.say("hi again").await }
+		});
+		tokio::task::yield_now().await;
+		assert_eq!(chat.queue_depth(), 2);
+
+		tokio::select! {
+			result = expiring => assert!(matches!(result.unwrap(), Err(SendError::DeadlineExceeded))),
+			event = chat.next() => panic!("unexpected event: {event:?}")
+		}
+		// Timers have millisecond granularity.
+		assert!((Duration::from_secs(5)..Duration::from_secs(6)).contains(&(Instant::now() - start)));
+		assert_eq!(chat.queue_depth(), 1);
+		assert!(!waiting.is_finished());
+	}
+}
@@ -0,0 +1,71 @@
+// Copyright 2026 pyke.io
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+/// Controls how [`Chat`](super::Chat) reconnects after the connection drops or Twitch asks it to reconnect.
+///
+/// Reconnection attempts are delayed with jittered exponential backoff: the `n`th consecutive attempt waits a random
+/// duration between half of and the full `min(initial_delay * 2^n, max_delay)`. Only the first reconnect after Twitch
+/// sends a `RECONNECT` command is made immediately.
+///
+/// An attempt only counts as successful once the server welcomes us, so a server that accepts connections but closes
+/// them (or rejects our credentials) right away is still subject to backoff.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReconnectPolicy {
+	/// The base delay before the first reconnection attempt.
+	pub initial_delay: Duration,
+	/// The maximum delay between two reconnection attempts.
+	pub max_delay: Duration,
+	/// The number of consecutive failed attempts after which the stream gives up, yielding the last error (if any) and
+	/// ending. `None` retries forever.
+	pub max_attempts: Option<u32>
+}
+
+impl Default for ReconnectPolicy {
+	fn default() -> Self {
+		Self {
+			initial_delay: Duration::from_secs(1),
+			max_delay: Duration::from_secs(60),
+			max_attempts: None
+		}
+	}
+}
+
+impl ReconnectPolicy {
+	pub(crate) fn delay(&self, attempt: u32) -> Duration {
+		let delay = self
+			.initial_delay
+			.saturating_mul(1u32.checked_shl(attempt).unwrap_or(u32::MAX))
+			.min(self.max_delay);
+		delay.mul_f64(0.5 + fastrand::f64() * 0.5)
+	}
+
+	pub(crate) fn should_retry(&self, attempt: u32) -> bool {
+		self.max_attempts.is_none_or(|max| attempt < max)
+	}
+}
+
+/// Why a [`Chat`](super::Chat) connection was lost.
+#[derive(Debug)]
+pub enum DisconnectReason {
+	/// Twitch sent a `RECONNECT` command, typically ahead of server maintenance.
+	ServerRequested,
+	/// The server closed the connection.
+	Closed,
+	/// Twitch rejected our credentials, and the [identity](super::TwitchIdentity::invalidate) will provide new ones.
+	AuthenticationFailed,
+	/// The connection failed.
+	Error(super::Error)
+}
@@ -0,0 +1,442 @@
+// Copyright 2026 pyke.io
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+	collections::{HashMap, VecDeque},
+	error::Error as StdError,
+	fmt,
+	sync::{
+		Arc, Mutex, PoisonError,
+		atomic::{AtomicUsize, Ordering}
+	},
+	time::Duration
+};
+
+use futures_channel::{mpsc, oneshot};
+use irc::proto::{Command, Message, message::Tag};
+use tokio::time::Instant;
+use uuid::Uuid;
+
+/// The reason Twitch gave for rejecting a message, parsed from the `msg-id` tag of the `NOTICE` sent in response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SendRejection {
+	/// The account is banned from the channel.
+	Banned,
+	/// The account is currently timed out in the channel.
+	TimedOut,
+	/// The channel is suspended.
+	ChannelSuspended,
+	/// The message was identical to the previous message sent less than 30 seconds ago.
+	Duplicate,
+	/// The channel is in emote-only mode.
+	EmoteOnly,
+	/// The channel is in followers-only mode and the account doesn't follow the channel (or hasn't followed for long
+	/// enough).
+	FollowersOnly,
+	/// The channel is in subscribers-only mode.
+	SubsOnly,
+	/// The channel is in slow mode and the account sent a message too recently.
+	SlowMode,
+	/// The channel is in unique-chat (r9k) mode and the message wasn't unique.
+	UniqueChat,
+	/// The account is sending messages too quickly.
+	RateLimited,
+	/// The message was held or rejected by AutoMod or the channel's blocked terms.
+	Rejected,
+	/// The channel requires a verified email address to chat.
+	VerifiedEmailRequired,
+	/// The channel requires a verified phone number to chat.
+	VerifiedPhoneRequired,
+	/// Any other rejection, containing the raw `msg-id`.
+	Other(String)
+}
+
+impl SendRejection {
+	pub(crate) fn from_msg_id(msg_id: &str) -> Self {
+		match msg_id {
+			"msg_banned" => Self::Banned,
+			"msg_timedout" => Self::TimedOut,
+			"msg_channel_suspended" => Self::ChannelSuspended,
+			"msg_duplicate" => Self::Duplicate,
+			"msg_emoteonly" => Self::EmoteOnly,
+			"msg_followersonly" | "msg_followersonly_followed" | "msg_followersonly_zero" => Self::FollowersOnly,
+			"msg_subsonly" => Self::SubsOnly,
+			"msg_slowmode" => Self::SlowMode,
+			"msg_r9k" => Self::UniqueChat,
+			"msg_ratelimit" => Self::RateLimited,
+			"msg_rejected" | "msg_rejected_mandatory" => Self::Rejected,
+			"msg_verified_email" => Self::VerifiedEmailRequired,
+			"msg_requires_verified_phone_number" => Self::VerifiedPhoneRequired,
+			x => Self::Other(x.to_owned())
+		}
+	}
+}
+
+#[derive(Debug)]
+pub enum SendError {
+	/// The connection uses an [`Anonymous`](super::Anonymous) identity, which cannot send messages.
+	Anonymous,
+	/// The message was empty, contained a line break, or was a chat command like `/ban` or `.color`. Commands aren't
+	/// acknowledged like messages, and most no longer work over IRC; use the [Helix API](super::helix) instead.
+	InvalidMessage,
+	/// The [`Chat`](super::Chat) was dropped or lost its connection before Twitch acknowledged the message.
+	Disconnected,
+	/// The message couldn't be sent within the sender's [deadline](ChatSender::with_deadline) without exceeding the
+	/// [rate limits](super::RateLimits).
+	DeadlineExceeded,
+	/// Twitch didn't acknowledge the message in time. It may have been dropped silently, or may still show up in chat.
+	Unacknowledged,
+	/// The channel rejected the message.
+	Rejected {
+		reason: SendRejection,
+		/// The human-readable explanation sent by Twitch.
+		message: String
+	}
+}
+
+impl fmt::Display for SendError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Anonymous => f.write_str("anonymous connections cannot send messages"),
+			Self::InvalidMessage => f.write_str("message must be non-empty, contain no line breaks, and not be a command"),
+			Self::Disconnected => f.write_str("connection closed before the message was acknowledged"),
+			Self::DeadlineExceeded => f.write_str("message could not be sent before its deadline"),
+			Self::Unacknowledged => f.write_str("message was not acknowledged in time"),
+			Self::Rejected { reason, message } => f.write_fmt(format_args!("message was rejected ({reason:?}): {message}"))
+		}
+	}
+}
+
+impl StdError for SendError {}
+
+pub(crate) type PendingSend = oneshot::Sender<Result<Option<Uuid>, SendError>>;
+
+/// How long to wait for Twitch to acknowledge a sent message before failing it with [`SendError::Unacknowledged`].
+const ACKNOWLEDGEMENT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A message waiting for the rate limiter to allow it to be sent.
+#[derive(Debug)]
+pub(crate) struct QueuedMessage {
+	pub message: Message,
+	pub channel: String,
+	pub tx: PendingSend,
+	pub deadline: Option<Instant>
+}
+
+/// Sent messages awaiting acknowledgement, per channel, along with when to give up on them.
+#[derive(Debug, Default)]
+pub(crate) struct PendingSends(Mutex<HashMap<String, VecDeque<(PendingSend, Instant)>>>);
+
+impl PendingSends {
+	pub(crate) fn push(&self, channel: &str, tx: PendingSend, now: Instant) {
+		self.0
+			.lock()
+			.unwrap_or_else(PoisonError::into_inner)
+			.entry(channel.to_owned())
+			.or_default()
+			.push_back((tx, now + ACKNOWLEDGEMENT_TIMEOUT));
+	}
+
+	/// Fails sends that haven't been acknowledged in time, returning when the next remaining send will time out.
+	///
+	/// Without this, a message Twitch never responds to would be matched with the acknowledgement of the next message
+	/// sent to the same channel, and every result after it would be off by one.
+	pub(crate) fn expire(&self, now: Instant) -> Option<Instant> {
+		let mut pending = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+		let mut next_expiry: Option<Instant> = None;
+		pending.retain(|_, sends| {
+			// Sends are pushed in order, so the oldest are always at the front.
+			while sends.front().is_some_and(|(_, expires_at)| *expires_at <= now) {
+				let (tx, _) = sends.pop_front().expect("queue is not empty");
+				let _ = tx.send(Err(SendError::Unacknowledged));
+			}
+			if let Some((_, expires_at)) = sends.front() {
+				next_expiry = Some(next_expiry.map_or(*expires_at, |next| next.min(*expires_at)));
+			}
+			!sends.is_empty()
+		});
+		next_expiry
+	}
+
+	pub(crate) fn clear(&self) {
+		self.0.lock().unwrap_or_else(PoisonError::into_inner).clear();
+	}
+
+	fn resolve(&self, target: &str, result: Result<Option<Uuid>, SendError>) -> bool {
+		let mut pending = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+		match pending.get_mut(target.trim_start_matches('#')).and_then(VecDeque::pop_front) {
+			Some((tx, _)) => {
+				let _ = tx.send(result);
+				true
+			}
+			None => false
+		}
+	}
+
+	/// Twitch acknowledges each `PRIVMSG` we send with either a `USERSTATE` carrying the message's `id` (accepted) or
+	/// a `NOTICE` (rejected), in the order the messages were sent to each channel.
+	///
+	/// Returns whether the message acknowledged a pending send.
+	pub(crate) fn handle_message(&self, message: &Message) -> bool {
+		match &message.command {
+			Command::Raw(command, args) if command == "USERSTATE" => {
+				// A `USERSTATE` is also sent upon joining a channel; only those acknowledging a message have an `id`.
+				let (Some(target), Some(id)) = (args.first(), find_tag(message, "id")) else {
+					return false;
+				};
+				self.resolve(target, Ok(id.parse().ok()))
+			}
+			Command::NOTICE(target, text) => match find_tag(message, "msg-id") {
+				// Rejections all have a `msg_` prefix. Other notices, like room mode changes (`followers_on_zero`,
+				// `slow_off`, ...), are announced to the whole channel rather than sent in response to a message.
+				Some(msg_id) if msg_id.starts_with("msg_") => self.resolve(
+					target,
+					Err(SendError::Rejected {
+						reason: SendRejection::from_msg_id(msg_id),
+						message: text.clone()
+					})
+				),
+				_ => false
+			},
+			_ => false
+		}
+	}
+}
+
+pub(crate) fn find_tag<'m>(message: &'m Message, key: &str) -> Option<&'m str> {
+	message.tags.as_ref()?.iter().find(|tag| tag.0 == key)?.1.as_deref()
+}
+
+/// A handle used to send messages to a Twitch channel, obtained via [`Chat::sender`](super::Chat::sender).
+///
+/// The handle is cheap to clone and can be moved to another task, but messages are only actually sent (and
+/// acknowledged) while the [`Chat`](super::Chat) it was created from is being polled.
+///
+/// ```no_run
+/// use brainrot::twitch::{Authenticated, Chat};
+/// use futures_util::StreamExt;
+///
+/// # #[tokio::main]
+/// # async fn main() -> anyhow::Result<()> {
+/// let mut client = Chat::new("miyukiwei", Authenticated("yukifan4", "yfvzjqb705z12hrhy1zkwa9xt7v662")).await?;
+/// let sender = client.sender();
+/// tokio::spawn(async move { while client.next().await.is_some() {} });
+///
+/// sender.say("hello chat").await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChatSender {
+	sender: mpsc::UnboundedSender<QueuedMessage>,
+	channel: String,
+	authenticated: bool,
+	queue_depth: Arc<AtomicUsize>,
+	deadline: Option<Duration>
+}
+
+impl ChatSender {
+	pub(crate) fn new(sender: mpsc::UnboundedSender<QueuedMessage>, channel: String, authenticated: bool, queue_depth: Arc<AtomicUsize>) -> Self {
+		Self {
+			sender,
+			channel,
+			authenticated,
+			queue_depth,
+			deadline: None
+		}
+	}
+
+	/// Returns a new sender on the same connection which sends messages to a different channel. The channel should
+	/// already be joined via [`Chat::join`](super::Chat::join).
+	pub fn with_channel(&self, channel: impl AsRef<str>) -> Self {
+		Self {
+			channel: channel.as_ref().trim_start_matches('#').to_ascii_lowercase(),
+			..self.clone()
+		}
+	}
+
+	/// Returns a new sender whose messages fail with [`SendError::DeadlineExceeded`] if the
+	/// [rate limits](super::RateLimits) don't allow them to be sent within `deadline` of being queued.
+	///
+	/// By default, messages wait in the queue for as long as necessary.
+	pub fn with_deadline(&self, deadline: Duration) -> Self {
+		Self {
+			deadline: Some(deadline),
+			..self.clone()
+		}
+	}
+
+	/// The name of the channel this sender sends messages to.
+	pub fn channel(&self) -> &str {
+		&self.channel
+	}
+
+	/// The number of messages waiting to be sent on this connection, across all senders.
+	pub fn queue_depth(&self) -> usize {
+		self.queue_depth.load(Ordering::Relaxed)
+	}
+
+	/// Sends a message to the channel, waiting for Twitch to either accept or reject it.
+	///
+	/// Returns the ID of the sent message if Twitch provided one.
+	pub async fn say(&self, message: impl Into<String>) -> Result<Option<Uuid>, SendError> {
+		self.send(message.into(), None).await
+	}
+
+	/// Sends an action message to the channel, as if the message were sent with `/me`.
+	pub async fn me(&self, action: impl AsRef<str>) -> Result<Option<Uuid>, SendError> {
+		self.send(format!("\u{1}ACTION {}\u{1}", action.as_ref()), None).await
+	}
+
+	/// Sends a message to the channel as a reply to the message with the given ID.
+	pub async fn reply(&self, parent_id: Uuid, message: impl Into<String>) -> Result<Option<Uuid>, SendError> {
+		self.send(message.into(), Some(parent_id)).await
+	}
+
+	async fn send(&self, message: String, reply_to: Option<Uuid>) -> Result<Option<Uuid>, SendError> {
+		if !self.authenticated {
+			return Err(SendError::Anonymous);
+		}
+		if message.is_empty() || message.contains(['\r', '\n']) || is_command(&message) {
+			return Err(SendError::InvalidMessage);
+		}
+
+		let (tx, rx) = oneshot::channel();
+		self.queue_depth.fetch_add(1, Ordering::Relaxed);
+		self.sender
+			.unbounded_send(QueuedMessage {
+				message: Message {
+					tags: reply_to.map(|id| vec![Tag("reply-parent-msg-id".to_owned(), Some(id.to_string()))]),
+					prefix: None,
+					command: Command::PRIVMSG(format!("#{}", self.channel), message)
+				},
+				channel: self.channel.clone(),
+				tx,
+				deadline: self.deadline.map(|deadline| Instant::now() + deadline)
+			})
+			.map_err(|_| {
+				self.queue_depth.fetch_sub(1, Ordering::Relaxed);
+				SendError::Disconnected
+			})?;
+		rx.await.unwrap_or(Err(SendError::Disconnected))
+	}
+}
+
+/// Whether Twitch would interpret a message as a chat command, like `/ban` or `.color`, rather than text.
+fn is_command(message: &str) -> bool {
+	let mut chars = message.chars();
+	matches!(chars.next(), Some('/' | '.')) && chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::Duration;
+
+	use futures_channel::oneshot;
+	use irc::proto::Message;
+	use tokio::time::Instant;
+
+	use super::{PendingSends, SendError, SendRejection, is_command};
+
+	fn message(line: &str) -> Message {
+		line.parse().unwrap()
+	}
+
+	#[test]
+	fn join_userstate_does_not_acknowledge() {
+		let pending = PendingSends::default();
+		let (tx, mut rx) = oneshot::channel();
+		pending.push("miyukiwei", tx, Instant::now());
+
+		assert!(!pending.handle_message(&message(
+			"@badge-info=;badges=;color=;display-name=yukifan4;emote-sets=0;mod=0;subscriber=0;user-type= :tmi.twitch.tv USERSTATE #miyukiwei"
+		)));
+		assert!(rx.try_recv().unwrap().is_none());
+
+		assert!(pending.handle_message(&message(
+			"@badge-info=;badges=;color=;display-name=yukifan4;emote-sets=0;id=b34ccfc7-4977-403a-8a94-33c6bac34fb8;mod=0;subscriber=0;user-type= :tmi.twitch.tv USERSTATE #miyukiwei"
+		)));
+		let id = rx.try_recv().unwrap().unwrap().unwrap();
+		assert_eq!(id.unwrap().to_string(), "b34ccfc7-4977-403a-8a94-33c6bac34fb8");
+	}
+
+	#[test]
+	fn rejection_notices_reject() {
+		let pending = PendingSends::default();
+		let (tx, mut rx) = oneshot::channel();
+		pending.push("miyukiwei", tx, Instant::now());
+
+		assert!(pending.handle_message(&message("@msg-id=msg_followersonly :tmi.twitch.tv NOTICE #miyukiwei :This room is in followers-only mode.")));
+		match rx.try_recv().unwrap().unwrap() {
+			Err(SendError::Rejected { reason, .. }) => assert_eq!(reason, SendRejection::FollowersOnly),
+			result => panic!("unexpected result: {result:?}")
+		}
+	}
+
+	#[test]
+	fn mode_notices_do_not_acknowledge() {
+		let pending = PendingSends::default();
+		let (first_tx, mut first_rx) = oneshot::channel();
+		let (second_tx, mut second_rx) = oneshot::channel();
+		pending.push("miyukiwei", first_tx, Instant::now());
+		pending.push("miyukiwei", second_tx, Instant::now());
+
+		// Room mode changes are announced to the whole channel, and may arrive between a message and its USERSTATE.
+		for line in [
+			"@msg-id=followers_on_zero :tmi.twitch.tv NOTICE #miyukiwei :This room is now in followers-only mode.",
+			"@msg-id=slow_off :tmi.twitch.tv NOTICE #miyukiwei :This room is no longer in slow mode.",
+			"@msg-id=emote_only_on :tmi.twitch.tv NOTICE #miyukiwei :This room is now in emote-only mode."
+		] {
+			assert!(!pending.handle_message(&message(line)));
+		}
+		assert!(first_rx.try_recv().unwrap().is_none());
+
+		assert!(pending.handle_message(&message(
+			"@badge-info=;badges=;color=;display-name=yukifan4;emote-sets=0;id=b34ccfc7-4977-403a-8a94-33c6bac34fb8;mod=0;subscriber=0;user-type= :tmi.twitch.tv USERSTATE #miyukiwei"
+		)));
+		assert!(matches!(first_rx.try_recv().unwrap(), Some(Ok(Some(_)))));
+		assert!(second_rx.try_recv().unwrap().is_none());
+	}
+
+	#[test]
+	fn unacknowledged_sends_expire() {
+		let pending = PendingSends::default();
+		let now = Instant::now();
+		let (first_tx, mut first_rx) = oneshot::channel();
+		let (second_tx, mut second_rx) = oneshot::channel();
+		pending.push("miyukiwei", first_tx, now);
+		pending.push("miyukiwei", second_tx, now + Duration::from_secs(5));
+
+		let next = pending.expire(now + Duration::from_secs(10));
+		assert!(matches!(first_rx.try_recv().unwrap(), Some(Err(SendError::Unacknowledged))));
+		assert!(second_rx.try_recv().unwrap().is_none());
+		assert_eq!(next, Some(now + Duration::from_secs(15)));
+
+		// The next acknowledgement goes to the second send, not the expired one.
+		assert!(pending.handle_message(&message(
+			"@msg-id=msg_duplicate :tmi.twitch.tv NOTICE #miyukiwei :Your message is identical to the one you sent less than 30 seconds ago."
+		)));
+		assert!(matches!(second_rx.try_recv().unwrap(), Some(Err(SendError::Rejected { reason: SendRejection::Duplicate, .. }))));
+	}
+
+	#[test]
+	fn commands() {
+		assert!(is_command("/ban yukifan4"));
+		assert!(is_command(".color red"));
+		assert!(!is_command("... okay"));
+		assert!(!is_command("/ not a command"));
+		assert!(!is_command("hello"));
+	}
+}
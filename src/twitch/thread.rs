@@ -0,0 +1,278 @@
+// Copyright 2026 pyke.io
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{HashMap, VecDeque};
+
+use uuid::Uuid;
+
+use super::{ChatEvent, MessageSegment, ReplyInfo, User};
+
+/// A message remembered by a [`ThreadCache`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CachedMessage {
+	pub id: Uuid,
+	/// The name of the channel the message was sent in.
+	pub channel: String,
+	pub user: User,
+	pub sent_at_ms: i64,
+	pub reply_to: Option<ReplyInfo>,
+	/// Whether the message was sent with `/me`.
+	pub is_action: bool,
+	pub contents: Vec<MessageSegment>
+}
+
+impl CachedMessage {
+	/// The ID of the thread this message belongs to, if it is a reply.
+	pub fn thread_id(&self) -> Option<Uuid> {
+		self.reply_to.as_ref().map(|reply| reply.thread_id)
+	}
+}
+
+/// The message a reply responds to, as resolved by [`ThreadCache::parent`].
+#[derive(Debug, Clone, Copy)]
+pub enum ReplyParent<'c> {
+	/// The parent message is in the cache.
+	Cached(&'c CachedMessage),
+	/// The parent message was sent before the cache saw it (e.g. before connecting) or has since been evicted. Twitch
+	/// includes the parent's author & text with every reply, so it can still be quoted.
+	Uncached(&'c ReplyInfo)
+}
+
+impl ReplyParent<'_> {
+	pub fn id(&self) -> Uuid {
+		match self {
+			Self::Cached(message) => message.id,
+			Self::Uncached(reply) => reply.parent_id
+		}
+	}
+
+	pub fn username(&self) -> &str {
+		match self {
+			Self::Cached(message) => &message.user.username,
+			Self::Uncached(reply) => &reply.parent_username
+		}
+	}
+
+	pub fn display_name(&self) -> &str {
+		match self {
+			Self::Cached(message) => &message.user.display_name,
+			Self::Uncached(reply) => &reply.parent_display_name
+		}
+	}
+}
+
+/// Keeps a bounded cache of recent chat messages in order to resolve replies into threads.
+///
+/// Feed every event received from [`Chat`](super::Chat) into [`ThreadCache::handle_event`]. Once the cache holds
+/// `capacity` messages, the oldest messages are forgotten. Messages deleted by moderators are removed from the cache.
+///
+/// ```
+/// use brainrot::twitch::{ChatEvent, ReplyParent, ThreadCache, parse_line};
+///
+/// let mut threads = ThreadCache::new(1000);
+/// let message = "@emotes=;id=6d2a8c4e-0f57-4d8b-9f0a-3b6f0e9b1c2d;tmi-sent-ts=1700000000000;user-id=3 \
+///     :miyu!miyu@miyu.tmi.twitch.tv PRIVMSG #miyukiwei :hello";
+/// threads.handle_event(&parse_line(message)?.unwrap());
+///
+/// let reply = "@emotes=;id=b34ccfc7-4977-403a-8a94-33c6bac34fb8;tmi-sent-ts=1700000001000;user-id=2;\
+///     reply-parent-msg-id=6d2a8c4e-0f57-4d8b-9f0a-3b6f0e9b1c2d;reply-parent-user-login=miyu;reply-parent-msg-body=hello \
+///     :yukifan4!yukifan4@yukifan4.tmi.twitch.tv PRIVMSG #miyukiwei :@miyu hi";
+/// let event = parse_line(reply)?.unwrap();
+/// threads.handle_event(&event);
+///
+/// # let ChatEvent::Message { reply_to: Some(reply), .. } = event else { unreachable!() };
+/// let parent = threads.parent(&reply);
+/// assert!(matches!(parent, ReplyParent::Cached(_)));
+/// println!("replying to @{}: {}", parent.display_name(), reply.parent_message);
+/// # Ok::<_, brainrot::twitch::ParseError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct ThreadCache {
+	capacity: usize,
+	order: VecDeque<Uuid>,
+	messages: HashMap<Uuid, CachedMessage>
+}
+
+impl ThreadCache {
+	pub fn new(capacity: usize) -> Self {
+		Self {
+			capacity,
+			order: VecDeque::with_capacity(capacity),
+			messages: HashMap::with_capacity(capacity)
+		}
+	}
+
+	/// Updates the cache with an event: messages (including messages with Bits) are remembered, and deleted messages
+	/// are forgotten.
+	pub fn handle_event(&mut self, event: &ChatEvent) {
+		match event {
+			ChatEvent::Message {
+				channel,
+				id,
+				user,
+				sent_at_ms,
+				reply_to,
+				is_action,
+				contents,
+				..
+			} => self.insert(CachedMessage {
+				id: *id,
+				channel: channel.name.clone(),
+				user: user.clone(),
+				sent_at_ms: *sent_at_ms,
+				reply_to: reply_to.clone(),
+				is_action: *is_action,
+				contents: contents.clone()
+			}),
+			ChatEvent::SendBits {
+				channel,
+				id,
+				user,
+				sent_at_ms,
+				reply_to,
+				is_action,
+				segments,
+				..
+			} => self.insert(CachedMessage {
+				id: *id,
+				channel: channel.name.clone(),
+				user: user.clone(),
+				sent_at_ms: *sent_at_ms,
+				reply_to: reply_to.clone(),
+				is_action: *is_action,
+				contents: segments.clone()
+			}),
+			ChatEvent::MessageDeleted { id, .. } => {
+				self.retain(|message| message.id != *id);
+			}
+			ChatEvent::UserTimedOut { channel, user_id, .. } | ChatEvent::UserBanned { channel, user_id, .. } => {
+				self.retain(|message| message.channel != channel.name || message.user.id != *user_id);
+			}
+			ChatEvent::ChatCleared { channel, .. } => {
+				self.retain(|message| message.channel != channel.name);
+			}
+			_ => {}
+		}
+	}
+
+	/// Forgets all messages for which `f` returns `false`. `order` must only ever contain cached IDs; otherwise a
+	/// message that is deleted & later re-inserted would appear twice in threads, and be evicted too early.
+	fn retain(&mut self, mut f: impl FnMut(&CachedMessage) -> bool) {
+		let before = self.messages.len();
+		self.messages.retain(|_, message| f(message));
+		if self.messages.len() != before {
+			self.order.retain(|id| self.messages.contains_key(id));
+		}
+	}
+
+	/// Remembers a message, evicting the oldest message if the cache is full.
+	///
+	/// Messages whose ID is already cached are ignored.
+	pub fn insert(&mut self, message: CachedMessage) {
+		if self.capacity == 0 || self.messages.contains_key(&message.id) {
+			return;
+		}
+		self.order.push_back(message.id);
+		self.messages.insert(message.id, message);
+		while self.messages.len() > self.capacity {
+			let Some(id) = self.order.pop_front() else {
+				break;
+			};
+			self.messages.remove(&id);
+		}
+	}
+
+	pub fn get(&self, id: &Uuid) -> Option<&CachedMessage> {
+		self.messages.get(id)
+	}
+
+	/// Resolves the message a reply responds to.
+	pub fn parent<'c>(&'c self, reply: &'c ReplyInfo) -> ReplyParent<'c> {
+		match self.messages.get(&reply.parent_id) {
+			Some(message) => ReplyParent::Cached(message),
+			None => ReplyParent::Uncached(reply)
+		}
+	}
+
+	/// Returns the cached messages in a thread, oldest first, including the message that started the thread if it is
+	/// still cached.
+	pub fn thread(&self, thread_id: Uuid) -> impl Iterator<Item = &CachedMessage> + '_ {
+		self.order
+			.iter()
+			.filter_map(|id| self.messages.get(id))
+			.filter(move |message| message.id == thread_id || message.thread_id() == Some(thread_id))
+	}
+
+	/// The number of messages currently cached.
+	pub fn len(&self) -> usize {
+		self.messages.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.messages.is_empty()
+	}
+
+	pub fn clear(&mut self) {
+		self.order.clear();
+		self.messages.clear();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::ThreadCache;
+	use crate::twitch::parse_line;
+
+	const MESSAGE: &str = "@badges=;color=;display-name=yukifan4;emotes=;id=b34ccfc7-4977-403a-8a94-33c6bac34fb8;room-id=1;tmi-sent-ts=1700000000002;user-id=2 :yukifan4!yukifan4@yukifan4.tmi.twitch.tv PRIVMSG #miyukiwei :hi";
+	const CHEER: &str = "@badges=;bits=100;color=;display-name=yukifan4;emotes=;id=6d2a8c4e-0f57-4d8b-9f0a-3b6f0e9b1c2d;room-id=1;tmi-sent-ts=1700000000000;user-id=2 :yukifan4!yukifan4@yukifan4.tmi.twitch.tv PRIVMSG #miyukiwei :Cheer100";
+
+	#[test]
+	fn caches_cheers_once() {
+		let mut threads = ThreadCache::new(2);
+		threads.handle_event(&parse_line(CHEER).unwrap().unwrap());
+		threads.handle_event(&parse_line(CHEER).unwrap().unwrap());
+		assert_eq!(threads.len(), 1);
+
+		let id = "6d2a8c4e-0f57-4d8b-9f0a-3b6f0e9b1c2d".parse().unwrap();
+		assert_eq!(threads.get(&id).unwrap().channel, "miyukiwei");
+		assert_eq!(threads.thread(id).count(), 1);
+	}
+
+	#[test]
+	fn reinserted_messages_are_not_duplicated() {
+		let mut threads = ThreadCache::new(2);
+		let cheer = parse_line(CHEER).unwrap().unwrap();
+		let id = "6d2a8c4e-0f57-4d8b-9f0a-3b6f0e9b1c2d".parse().unwrap();
+		threads.handle_event(&cheer);
+		threads.handle_event(
+			&parse_line(
+				"@login=yukifan4;room-id=;target-msg-id=6d2a8c4e-0f57-4d8b-9f0a-3b6f0e9b1c2d;tmi-sent-ts=1700000000001 :tmi.twitch.tv CLEARMSG #miyukiwei :Cheer100"
+			)
+			.unwrap()
+			.unwrap()
+		);
+		assert!(threads.is_empty());
+
+		threads.handle_event(&cheer);
+		assert_eq!(threads.thread(id).count(), 1);
+
+		// The re-inserted message is the oldest, so it's evicted only once the cache overflows.
+		threads.handle_event(&parse_line(MESSAGE).unwrap().unwrap());
+		assert!(threads.get(&id).is_some());
+		threads.handle_event(&parse_line(&MESSAGE.replace("b34ccfc7", "c45ddfd8")).unwrap().unwrap());
+		assert!(threads.get(&id).is_none());
+		assert_eq!(threads.len(), 2);
+	}
+}
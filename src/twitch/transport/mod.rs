@@ -0,0 +1,168 @@
+// Copyright 2026 pyke.io
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+	fmt,
+	pin::Pin,
+	sync::Arc,
+	task::{Context, Poll}
+};
+
+#[cfg(feature = "twitch-websocket")]
+use futures_channel::mpsc;
+use futures_util::{Stream, StreamExt};
+use irc::{
+	client::{Client, ClientStream, prelude::Config},
+	proto::{Command, Message}
+};
+
+use super::{Capabilities, Error, identity::DynIdentity};
+
+#[cfg(feature = "twitch-websocket")]
+mod websocket;
+#[cfg(feature = "twitch-websocket")]
+use self::websocket::WebSocketConnection;
+
+const TWITCH_IRC_SERVER: &str = "irc.chat.twitch.tv";
+#[cfg(feature = "twitch-websocket")]
+const TWITCH_IRC_WEBSOCKET_SERVER: &str = "irc-ws.chat.twitch.tv";
+
+/// How [`Chat`](super::Chat) connects to Twitch.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Transport {
+	/// Plain IRC over TCP, to `irc.chat.twitch.tv` on port `6697` (TLS) or `6667`.
+	#[default]
+	Tcp,
+	/// IRC over WebSocket, to `irc-ws.chat.twitch.tv` on port `443` (TLS) or `80`. Useful where only HTTP(S) egress is
+	/// allowed.
+	#[cfg(feature = "twitch-websocket")]
+	WebSocket
+}
+
+impl Transport {
+	pub(crate) fn default_server(&self) -> &'static str {
+		match self {
+			Self::Tcp => TWITCH_IRC_SERVER,
+			#[cfg(feature = "twitch-websocket")]
+			Self::WebSocket => TWITCH_IRC_WEBSOCKET_SERVER
+		}
+	}
+}
+
+/// Everything needed to (re)establish a connection.
+#[derive(Clone)]
+pub(crate) struct ConnectOptions {
+	/// The connection's configuration, sans nickname & password, which are filled in from `identity` when connecting.
+	pub config: Config,
+	pub capabilities: Capabilities,
+	pub transport: Transport,
+	pub identity: Arc<dyn DynIdentity>,
+	pub anonymous_nickname: Option<String>
+}
+
+impl fmt::Debug for ConnectOptions {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ConnectOptions")
+			.field("config", &self.config)
+			.field("capabilities", &self.capabilities)
+			.field("transport", &self.transport)
+			.field("anonymous_nickname", &self.anonymous_nickname)
+			.finish_non_exhaustive()
+	}
+}
+
+/// A newly established connection.
+pub(crate) struct Established {
+	pub stream: IrcStream,
+	pub sender: IrcSender,
+	/// Whether we logged in with an access token.
+	pub authenticated: bool
+}
+
+pub(crate) async fn connect(mut options: ConnectOptions) -> Result<Established, Error> {
+	let credentials = options.identity.credentials().await?;
+	let authenticated = credentials.token.is_some();
+	options.config.nickname = Some(match (options.anonymous_nickname.take(), credentials.token.is_none()) {
+		(Some(nickname), true) => nickname,
+		_ => credentials.username
+	});
+	options.config.password = credentials.token.map(|token| format!("oauth:{token}"));
+
+	let (stream, sender) = match options.transport {
+		Transport::Tcp => {
+			let mut client = Client::from_config(options.config).await?;
+			let capabilities = options.capabilities.to_request();
+			if !capabilities.is_empty() {
+				client.send_cap_req(&capabilities)?;
+			}
+			client.identify()?;
+			(IrcStream::Tcp(Box::new(client.stream()?)), IrcSender::Tcp(client.sender()))
+		}
+		#[cfg(feature = "twitch-websocket")]
+		Transport::WebSocket => {
+			let (connection, sender) = WebSocketConnection::connect(&options).await?;
+			(IrcStream::WebSocket(Box::new(connection)), sender)
+		}
+	};
+	Ok(Established { stream, sender, authenticated })
+}
+
+/// The sending half of a connection.
+#[derive(Debug, Clone)]
+pub(crate) enum IrcSender {
+	Tcp(irc::client::Sender),
+	#[cfg(feature = "twitch-websocket")]
+	WebSocket(mpsc::UnboundedSender<Message>)
+}
+
+impl IrcSender {
+	pub fn send(&self, message: impl Into<Message>) -> Result<(), Error> {
+		match self {
+			Self::Tcp(sender) => Ok(sender.send(message)?),
+			#[cfg(feature = "twitch-websocket")]
+			Self::WebSocket(sender) => sender
+				.unbounded_send(message.into())
+				.map_err(|_| Error::Connection("connection closed".into()))
+		}
+	}
+
+	pub fn send_join(&self, channel: impl Into<String>) -> Result<(), Error> {
+		self.send(Command::JOIN(channel.into(), None, None))
+	}
+
+	pub fn send_part(&self, channel: impl Into<String>) -> Result<(), Error> {
+		self.send(Command::PART(channel.into(), None))
+	}
+}
+
+/// The receiving half of a connection.
+#[derive(Debug)]
+pub(crate) enum IrcStream {
+	Tcp(Box<ClientStream>),
+	#[cfg(feature = "twitch-websocket")]
+	WebSocket(Box<WebSocketConnection>)
+}
+
+impl Stream for IrcStream {
+	type Item = Result<Message, Error>;
+
+	fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		match self.get_mut() {
+			Self::Tcp(stream) => stream.poll_next_unpin(cx).map_err(Error::from),
+			#[cfg(feature = "twitch-websocket")]
+			Self::WebSocket(connection) => connection.poll_next_unpin(cx)
+		}
+	}
+}
@@ -0,0 +1,181 @@
+// Copyright 2026 pyke.io
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+	collections::VecDeque,
+	fmt,
+	pin::Pin,
+	task::{Context, Poll},
+	time::Duration
+};
+
+use futures_channel::mpsc;
+use futures_util::{SinkExt, Stream, StreamExt};
+use irc::proto::{CapSubCommand, Command, Message};
+use tokio::{
+	net::TcpStream,
+	time::{Instant, Sleep}
+};
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, tungstenite::Message as WsMessage};
+
+use super::{ConnectOptions, Error, IrcSender, TWITCH_IRC_SERVER};
+
+/// An IRC connection over WebSocket.
+///
+/// Twitch sends one or more `\r\n`-terminated IRC lines per text frame, and expects one line per frame from us. The
+/// `irc` crate normally answers `PING`s and detects dead connections for us; here, we have to do that ourselves.
+pub(crate) struct WebSocketConnection {
+	socket: WebSocketStream<MaybeTlsStream<TcpStream>>,
+	outgoing: mpsc::UnboundedReceiver<Message>,
+	/// Lines waiting to be written to the socket.
+	to_send: VecDeque<String>,
+	/// Lines received in a frame that haven't been yielded yet.
+	received: VecDeque<Message>,
+	ping_interval: Duration,
+	ping_timeout: Duration,
+	awaiting_pong: bool,
+	timer: Pin<Box<Sleep>>
+}
+
+impl fmt::Debug for WebSocketConnection {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("WebSocketConnection")
+			.field("to_send", &self.to_send)
+			.field("received", &self.received)
+			.field("awaiting_pong", &self.awaiting_pong)
+			.finish_non_exhaustive()
+	}
+}
+
+impl WebSocketConnection {
+	pub(super) async fn connect(options: &ConnectOptions) -> Result<(Self, IrcSender), Error> {
+		let config = &options.config;
+		let (scheme, default_port) = if config.use_tls() { ("wss", 443) } else { ("ws", 80) };
+		let url = format!("{scheme}://{}:{}", config.server()?, config.port.unwrap_or(default_port));
+		let (socket, _) = tokio_tungstenite::connect_async(url).await?;
+
+		let (sender, outgoing) = mpsc::unbounded();
+		let sender = IrcSender::WebSocket(sender);
+		// The same registration sequence `irc::client::Client::identify` sends.
+		let capabilities = options.capabilities.to_request();
+		if !capabilities.is_empty() {
+			let capabilities = capabilities.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(" ");
+			sender.send(Command::CAP(None, CapSubCommand::REQ, None, Some(capabilities)))?;
+		}
+		sender.send(Command::CAP(None, CapSubCommand::END, None, None))?;
+		if !config.password().is_empty() {
+			sender.send(Command::PASS(config.password().to_owned()))?;
+		}
+		sender.send(Command::NICK(config.nickname()?.to_owned()))?;
+		sender.send(Command::USER(config.username().to_owned(), "0".to_owned(), config.real_name().to_owned()))?;
+
+		let ping_interval = Duration::from_secs(config.ping_time().into());
+		Ok((
+			Self {
+				socket,
+				outgoing,
+				to_send: VecDeque::new(),
+				received: VecDeque::new(),
+				ping_interval,
+				ping_timeout: Duration::from_secs(config.ping_timeout().into()),
+				awaiting_pong: false,
+				timer: Box::pin(tokio::time::sleep(ping_interval))
+			},
+			sender
+		))
+	}
+
+	/// Pushes back the keepalive `PING` after receiving anything from the server.
+	fn reset_keepalive(&mut self) {
+		self.awaiting_pong = false;
+		self.timer.as_mut().reset(Instant::now() + self.ping_interval);
+	}
+
+	fn receive_frame(&mut self, text: &str) {
+		for line in text.split("\r\n").filter(|line| !line.is_empty()) {
+			// Skip lines we can't parse rather than dropping the connection over them.
+			let message: Message = match line.parse() {
+				Ok(message) => message,
+				Err(e) => {
+					tracing::warn!("ignoring malformed line {line:?}: {e}");
+					continue;
+				}
+			};
+			if let Command::PING(server, _) = &message.command {
+				self.to_send.push_back(Message::from(Command::PONG(server.clone(), None)).to_string());
+			}
+			self.received.push_back(message);
+		}
+	}
+}
+
+impl Stream for WebSocketConnection {
+	type Item = Result<Message, Error>;
+
+	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		let this = &mut *self;
+
+		while let Poll::Ready(Some(message)) = this.outgoing.poll_next_unpin(cx) {
+			this.to_send.push_back(message.to_string());
+		}
+
+		while this.received.is_empty() {
+			match this.socket.poll_next_unpin(cx) {
+				Poll::Ready(Some(Ok(WsMessage::Text(text)))) => {
+					this.reset_keepalive();
+					this.receive_frame(&text);
+				}
+				Poll::Ready(Some(Ok(WsMessage::Close(_)))) | Poll::Ready(None) => return Poll::Ready(None),
+				// Pings are answered by tungstenite; any other frame still tells us the connection is alive.
+				Poll::Ready(Some(Ok(_))) => this.reset_keepalive(),
+				Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e.into()))),
+				Poll::Pending => break
+			}
+		}
+
+		if this.timer.as_mut().poll(cx).is_ready() {
+			if this.awaiting_pong {
+				return Poll::Ready(Some(Err(Error::PingTimeout)));
+			}
+			this.awaiting_pong = true;
+			this.to_send
+				.push_back(Message::from(Command::PING(TWITCH_IRC_SERVER.to_owned(), None)).to_string());
+			this.timer.as_mut().reset(Instant::now() + this.ping_timeout);
+			// Register the timer with the new deadline.
+			let _ = this.timer.as_mut().poll(cx);
+		}
+
+		while !this.to_send.is_empty() {
+			match this.socket.poll_ready_unpin(cx) {
+				Poll::Ready(Ok(())) => {
+					// `Message`'s `Display` includes the trailing `\r\n`, which Twitch doesn't want over WebSocket.
+					let line = this.to_send.pop_front().expect("queue is not empty");
+					if let Err(e) = this.socket.start_send_unpin(WsMessage::text(line.trim_end_matches("\r\n"))) {
+						return Poll::Ready(Some(Err(e.into())));
+					}
+				}
+				Poll::Ready(Err(e)) => return Poll::Ready(Some(Err(e.into()))),
+				Poll::Pending => break
+			}
+		}
+		if let Poll::Ready(Err(e)) = this.socket.poll_flush_unpin(cx) {
+			return Poll::Ready(Some(Err(e.into())));
+		}
+
+		match this.received.pop_front() {
+			Some(message) => Poll::Ready(Some(Ok(message))),
+			None => Poll::Pending
+		}
+	}
+}
@@ -53,3 +53,19 @@ pub fn get_utf8_slice(s: &str, start: usize, end: usize) -> Option<&str> {
 	}
 	Some(&s[start_pos..*iter.peek()?])
 }
+
+/// Appends `s` to `out`, percent-encoding everything but unreserved characters, for use in a query string or
+/// `application/x-www-form-urlencoded` body.
+#[cfg(feature = "twitch-helix")]
+pub fn push_url_encoded(out: &mut String, s: &str) {
+	use std::fmt::Write;
+
+	for byte in s.bytes() {
+		match byte {
+			b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => out.push(byte as char),
+			_ => {
+				let _ = write!(out, "%{byte:02X}");
+			}
+		}
+	}
+}
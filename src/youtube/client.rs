@@ -1,8 +1,9 @@
-use std::{error::Error as StdError, fmt, future::Future, sync::OnceLock, time::Duration};
+use std::{error::Error as StdError, fmt, future::Future, sync::OnceLock};
 
-use bytes::{Bytes, BytesMut};
+use bytes::Bytes;
 use http::{HeaderMap, HeaderName, HeaderValue, Method, Request, Uri, header, request::Builder as RequestBuilder, uri::PathAndQuery};
 
+pub use crate::executor::{RequestExecutor, Response};
 use crate::youtube::types::{
 	self, InnertubeRequest, InnertubeRequestContext, InnertubeRequestContextClient, browse::BrowseRequest, get_live_chat::GetLiveChatRequest,
 	video::VideoRequest
@@ -11,24 +12,6 @@ use crate::youtube::types::{
 pub(crate) const DEFAULT_CLIENT_NAME: &str = "WEB";
 pub(crate) const DEFAULT_CLIENT_VERSION: &str = "2.20250925.01.00";
 
-pub trait Response: Send + Sized {
-	type Error: StdError + Send;
-
-	fn status_code(&self) -> u16;
-
-	fn recv_chunk(&mut self) -> impl Future<Output = Result<Option<Bytes>, Self::Error>> + Send + Sync + '_;
-
-	fn recv_all(mut self) -> impl Future<Output = Result<BytesMut, Self::Error>> + Send {
-		async move {
-			let mut out = BytesMut::new();
-			while let Some(frame) = self.recv_chunk().await? {
-				out.extend_from_slice(&frame);
-			}
-			Ok(out)
-		}
-	}
-}
-
 pub(crate) trait ResponseExt: Response + Sized {
 	fn with_innertube_error(self) -> impl Future<Output = Result<Self, InnertubeError>>;
 }
@@ -85,15 +68,6 @@ impl fmt::Display for InnertubeError {
 
 impl StdError for InnertubeError {}
 
-pub trait RequestExecutor: Send + Sync + 'static {
-	type Response: Response + 'static;
-	type Error: StdError + Send;
-
-	fn make_request(&self, req: http::Request<Bytes>) -> impl Future<Output = Result<Self::Response, Self::Error>> + Send + Sync + '_;
-
-	fn sleep(dur: Duration) -> impl Future<Output = ()> + Send + Sync;
-}
-
 #[derive(Debug)]
 pub enum ClientError<E> {
 	BadRequest(http::Error),
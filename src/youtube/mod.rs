@@ -379,7 +379,7 @@ impl<E: RequestExecutor> Chat<E> {
 								.into_iter()
 								.filter_map(|act| ChatEvent::from_action(act.action))
 							{
-								yielder.r#yield(event).await;
+								yielder.y(event).await;
 							}
 
 							let Some(Continuation::Invalidation { continuation: next_token, .. }) = contents.live_chat_continuation.continuations.first()
@@ -416,7 +416,7 @@ impl<E: RequestExecutor> Chat<E> {
 									.into_iter()
 									.filter_map(|act| ChatEvent::from_action(act.action))
 								{
-									yielder.r#yield(event).await;
+									yielder.y(event).await;
 								}
 
 								let Some(Continuation::Invalidation { continuation: next_token, .. }) = contents.live_chat_continuation.continuations.first()
@@ -451,7 +451,7 @@ impl<E: RequestExecutor> Chat<E> {
 						let mut events = events;
 						loop {
 							for event in events.drain(..) {
-								yielder.r#yield(event).await;
+								yielder.y(event).await;
 							}
 
 							let mut continuation = context
@@ -478,7 +478,7 @@ impl<E: RequestExecutor> Chat<E> {
 
 							let Some(Continuation::Replay { continuation: next_token, .. }) = contents.live_chat_continuation.continuations.first() else {
 								for event in events.drain(..) {
-									yielder.r#yield(event).await;
+									yielder.y(event).await;
 								}
 								break;
 							};
@@ -532,7 +532,7 @@ impl<E: RequestExecutor> Chat<E> {
 								.into_iter()
 								.filter_map(|act| ChatEvent::from_action(act.action))
 							{
-								yielder.r#yield(event).await;
+								yielder.y(event).await;
 							}
 
 							let Some(Continuation::Timed { continuation: next_token, timeout_ms }) = contents.live_chat_continuation.continuations.first()
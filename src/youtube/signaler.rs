@@ -211,7 +211,7 @@ impl SignalerChannel {
 							self.aid = aid;
 						}
 
-						yielder.r#yield(()).await;
+						yielder.y(()).await;
 					}
 					Ok(None) => break,
 					Err(e) => return Err(SignalerError::Receive(e))
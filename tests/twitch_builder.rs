@@ -0,0 +1,103 @@
+// Copyright 2026 pyke.io
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "twitch")]
+
+use std::{
+	io::{BufRead, BufReader, Write},
+	net::TcpListener,
+	sync::mpsc,
+	thread,
+	time::Duration
+};
+
+use brainrot::twitch::{Anonymous, Authenticated, Capabilities, Chat, ChatBuilder, ChatEvent, IntoTwitchIdentity};
+use futures_util::StreamExt;
+
+const MESSAGE: &str = "@badge-info=;badges=;color=;display-name=miyukiwei;emotes=;id=b34ccfc7-4977-403a-8a94-33c6bac34fb8;mod=0;room-id=1;tmi-sent-ts=1;user-id=1;user-type= :miyukiwei!miyukiwei@miyukiwei.tmi.twitch.tv PRIVMSG #miyukiwei :hi\r\n";
+
+/// A server that welcomes the client once it has registered and sends a message once it has joined a channel. Every
+/// line received from the client is forwarded to the returned channel.
+fn serve() -> (u16, mpsc::Receiver<String>) {
+	let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+	let port = listener.local_addr().unwrap().port();
+	let (tx, rx) = mpsc::channel();
+	thread::spawn(move || {
+		let (socket, _) = listener.accept().unwrap();
+		let mut writer = socket.try_clone().unwrap();
+		for line in BufReader::new(socket).lines() {
+			let Ok(line) = line else {
+				break;
+			};
+			let response = match line.split(' ').next() {
+				Some("USER") => ":tmi.twitch.tv 001 justinfan24340 :Welcome, GLHF!\r\n",
+				Some("JOIN") => MESSAGE,
+				_ => ""
+			};
+			// Forward the line before responding, so it has been recorded by the time the client sees the response.
+			if tx.send(line).is_err() || writer.write_all(response.as_bytes()).is_err() {
+				break;
+			}
+		}
+	});
+	(port, rx)
+}
+
+/// Connects with `builder` and returns the lines the server received up to the first message.
+async fn run(builder: ChatBuilder, auth: impl IntoTwitchIdentity) -> Vec<String> {
+	let (port, rx) = serve();
+	let mut chat = builder
+		.server("127.0.0.1")
+		.port(port)
+		.tls(false)
+		.connect("miyukiwei", auth)
+		.await
+		.unwrap();
+	tokio::time::timeout(Duration::from_secs(5), async {
+		loop {
+			match chat.next().await {
+				Some(Ok(ChatEvent::Message { .. })) => break,
+				Some(Ok(_)) => {}
+				event => panic!("stream ended before the message arrived: {event:?}")
+			}
+		}
+	})
+	.await
+	.unwrap();
+	rx.try_iter().collect()
+}
+
+#[tokio::test]
+async fn requests_capabilities() {
+	let lines = run(Chat::builder(), Anonymous).await;
+	assert_eq!(lines[0], "CAP REQ :twitch.tv/commands twitch.tv/membership twitch.tv/tags");
+	assert!(lines.contains(&"NICK justinfan24340".to_owned()));
+	assert!(lines.contains(&"JOIN #miyukiwei".to_owned()));
+}
+
+#[tokio::test]
+async fn applies_options() {
+	let lines = run(Chat::builder().capabilities(Capabilities::none()).anonymous_nickname("justinfan1234"), Anonymous).await;
+	assert!(!lines.iter().any(|line| line.starts_with("CAP REQ")), "requested capabilities: {lines:?}");
+	assert!(lines.contains(&"NICK justinfan1234".to_owned()));
+	assert!(lines.contains(&"JOIN #miyukiwei".to_owned()));
+}
+
+#[tokio::test]
+async fn borrows_credentials() {
+	let (username, token) = (String::from("yukifan4"), String::from("yfvzjqb705z12hrhy1zkwa9xt7v662"));
+	let lines = run(Chat::builder(), Authenticated(username.as_str(), token.as_str())).await;
+	assert!(lines.contains(&"PASS oauth:yfvzjqb705z12hrhy1zkwa9xt7v662".to_owned()));
+	assert!(lines.contains(&"NICK yukifan4".to_owned()));
+}
@@ -0,0 +1,306 @@
+// Copyright 2026 pyke.io
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "twitch-eventsub")]
+
+use std::{
+	io,
+	sync::{Arc, Mutex},
+	time::Duration
+};
+
+use brainrot::twitch::{
+	RequestExecutor, Response,
+	eventsub::{Event, EventSub, Notification, Subscription},
+	helix
+};
+use bytes::Bytes;
+use futures_util::{SinkExt, StreamExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::{WebSocketStream, tungstenite::Message};
+
+/// An executor for tests that never make Helix requests.
+#[derive(Debug)]
+struct Offline;
+
+#[derive(Debug)]
+struct NoResponse;
+
+impl Response for NoResponse {
+	type Error = io::Error;
+
+	fn status_code(&self) -> u16 {
+		503
+	}
+
+	async fn recv_chunk(&mut self) -> Result<Option<Bytes>, Self::Error> {
+		Ok(None)
+	}
+}
+
+impl RequestExecutor for Offline {
+	type Response = NoResponse;
+	type Error = io::Error;
+
+	async fn make_request(&self, _: http::Request<Bytes>) -> Result<Self::Response, Self::Error> {
+		Err(io::Error::other("offline"))
+	}
+
+	async fn sleep(dur: Duration) {
+		tokio::time::sleep(dur).await;
+	}
+}
+
+type Requests = Arc<Mutex<Vec<http::Request<Bytes>>>>;
+
+/// An executor that records each request and responds with the given body.
+#[derive(Debug, Clone)]
+struct Recorder {
+	requests: Requests,
+	response: &'static str
+}
+
+#[derive(Debug)]
+struct RecordedResponse(Option<Bytes>);
+
+impl Response for RecordedResponse {
+	type Error = io::Error;
+
+	fn status_code(&self) -> u16 {
+		200
+	}
+
+	async fn recv_chunk(&mut self) -> Result<Option<Bytes>, Self::Error> {
+		Ok(self.0.take())
+	}
+}
+
+impl RequestExecutor for Recorder {
+	type Response = RecordedResponse;
+	type Error = io::Error;
+
+	async fn make_request(&self, request: http::Request<Bytes>) -> Result<Self::Response, Self::Error> {
+		self.requests.lock().unwrap().push(request);
+		Ok(RecordedResponse(Some(Bytes::from_static(self.response.as_bytes()))))
+	}
+
+	async fn sleep(dur: Duration) {
+		tokio::time::sleep(dur).await;
+	}
+}
+
+fn welcome(session_id: &str) -> Message {
+	Message::text(format!(
+		r#"{{"metadata":{{"message_id":"{session_id}-welcome","message_type":"session_welcome","message_timestamp":"2026-01-01T00:00:00Z"}},"payload":{{"session":{{"id":"{session_id}","status":"connected","keepalive_timeout_seconds":10,"reconnect_url":null,"connected_at":"2026-01-01T00:00:00Z"}}}}}}"#
+	))
+}
+
+fn reconnect(session_id: &str, url: &str) -> Message {
+	Message::text(format!(
+		r#"{{"metadata":{{"message_id":"{session_id}-reconnect","message_type":"session_reconnect","message_timestamp":"2026-01-01T00:00:00Z"}},"payload":{{"session":{{"id":"{session_id}","status":"reconnecting","keepalive_timeout_seconds":null,"reconnect_url":"{url}","connected_at":"2026-01-01T00:00:00Z"}}}}}}"#
+	))
+}
+
+fn notification(message_id: &str, kind: &str, event: &str) -> Message {
+	Message::text(format!(
+		r#"{{"metadata":{{"message_id":"{message_id}","message_type":"notification","message_timestamp":"2026-01-01T00:00:00Z","subscription_type":"{kind}","subscription_version":"1"}},"payload":{{"subscription":{{"id":"f1c2a387-161a-49f9-a165-0f21d7a4e1c4","status":"enabled","type":"{kind}","version":"1","condition":{{"broadcaster_user_id":"1337"}},"transport":{{"method":"websocket","session_id":"session"}},"created_at":"2026-01-01T00:00:00Z","cost":0}},"event":{event}}}}}"#
+	))
+}
+
+fn revocation(message_id: &str, kind: &str, status: &str) -> Message {
+	Message::text(format!(
+		r#"{{"metadata":{{"message_id":"{message_id}","message_type":"revocation","message_timestamp":"2026-01-01T00:00:00Z","subscription_type":"{kind}","subscription_version":"1"}},"payload":{{"subscription":{{"id":"f1c2a387-161a-49f9-a165-0f21d7a4e1c4","status":"{status}","type":"{kind}","version":"1","condition":{{"broadcaster_user_id":"1337"}},"transport":{{"method":"websocket","session_id":"session"}},"created_at":"2026-01-01T00:00:00Z","cost":0}}}}}}"#
+	))
+}
+
+const SUBSCRIPTION: &str = r#"{"data":[{"id":"f1c2a387-161a-49f9-a165-0f21d7a4e1c4","status":"enabled","type":"channel.poll.begin","version":"1","condition":{"broadcaster_user_id":"1337"},"transport":{"method":"websocket","session_id":"session","connected_at":"2026-01-01T00:00:00Z"},"created_at":"2026-01-01T00:00:00Z","cost":0}],"total":1,"total_cost":0,"max_total_cost":10}"#;
+
+const POLL: &str = r#"{"id":"1243456","broadcaster_user_id":"1337","broadcaster_user_login":"miyukiwei","broadcaster_user_name":"MiyukiWei","title":"Aren't shoes just really hard socks?","choices":[{"id":"123","title":"Yeah!"},{"id":"124","title":"No!"}],"channel_points_voting":{"is_enabled":true,"amount_per_vote":10},"started_at":"2026-01-01T00:00:00Z","ends_at":"2026-01-01T00:10:00Z"}"#;
+
+async fn listen() -> (TcpListener, String) {
+	let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+	let url = format!("ws://{}", listener.local_addr().unwrap());
+	(listener, url)
+}
+
+async fn accept(listener: &TcpListener) -> WebSocketStream<TcpStream> {
+	let (socket, _) = listener.accept().await.unwrap();
+	tokio_tungstenite::accept_async(socket).await.unwrap()
+}
+
+fn client() -> helix::Client<Offline> {
+	helix::Client::new(Offline, "client", "token").unwrap()
+}
+
+#[tokio::test]
+async fn receives_notifications() {
+	let (listener, url) = listen().await;
+	tokio::spawn(async move {
+		let mut socket = accept(&listener).await;
+		socket.send(welcome("session")).await.unwrap();
+		socket
+			.send(Message::text(
+				r#"{"metadata":{"message_id":"keepalive","message_type":"session_keepalive","message_timestamp":"2026-01-01T00:00:00Z"},"payload":{}}"#
+			))
+			.await
+			.unwrap();
+		socket.send(notification("1", "channel.poll.begin", POLL)).await.unwrap();
+		// A poll without its required fields, and a subscription type we don't parse.
+		socket.send(notification("2", "channel.poll.begin", r#"{"id":"1243456"}"#)).await.unwrap();
+		socket
+			.send(notification("3", "channel.ad_break.begin", r#"{"duration_seconds":60}"#))
+			.await
+			.unwrap();
+		while socket.next().await.is_some() {}
+	});
+
+	let mut events = EventSub::connect_to(client(), url).await.unwrap();
+	assert_eq!(events.session_id(), "session");
+
+	let mut notifications = Vec::new();
+	tokio::time::timeout(Duration::from_secs(5), async {
+		while notifications.len() < 3 {
+			match events.next().await {
+				Some(Ok(Event::Notification { message_id, notification, .. })) => notifications.push((message_id, notification)),
+				event => panic!("unexpected event: {event:?}")
+			}
+		}
+	})
+	.await
+	.unwrap();
+
+	let Notification::PollBegin(poll) = &*notifications[0].1 else {
+		panic!("expected a poll, got {:?}", notifications[0].1)
+	};
+	assert_eq!(poll.choices.len(), 2);
+	assert!(matches!(*notifications[1].1, Notification::Other(_)));
+	assert!(matches!(*notifications[2].1, Notification::Other(_)));
+	assert_eq!(notifications.iter().map(|(id, _)| id.as_str()).collect::<Vec<_>>(), ["1", "2", "3"]);
+}
+
+#[tokio::test]
+async fn drains_old_session_on_reconnect() {
+	let (listener, url) = listen().await;
+	let reconnect_url = url.clone();
+	tokio::spawn(async move {
+		let mut old_socket = accept(&listener).await;
+		old_socket.send(welcome("old")).await.unwrap();
+		old_socket.send(reconnect("old", &reconnect_url)).await.unwrap();
+
+		let mut new_socket = accept(&listener).await;
+		new_socket.send(welcome("new")).await.unwrap();
+		new_socket.send(notification("2", "channel.poll.begin", POLL)).await.unwrap();
+		// Sent to the old session after the new one was welcomed.
+		old_socket.send(notification("1", "channel.poll.begin", POLL)).await.unwrap();
+		old_socket.close(None).await.unwrap();
+		while new_socket.next().await.is_some() {}
+	});
+
+	let mut events = EventSub::connect_to(client(), url).await.unwrap();
+	let mut message_ids = Vec::new();
+	tokio::time::timeout(Duration::from_secs(5), async {
+		while message_ids.len() < 2 {
+			match events.next().await {
+				Some(Ok(Event::Notification { message_id, .. })) => message_ids.push(message_id),
+				event => panic!("unexpected event: {event:?}")
+			}
+		}
+	})
+	.await
+	.unwrap();
+	assert_eq!(message_ids, ["1", "2"]);
+	assert_eq!(events.session_id(), "new");
+}
+
+/// Serves a session that is welcomed and then kept open.
+async fn serve_session() -> String {
+	let (listener, url) = listen().await;
+	tokio::spawn(async move {
+		let mut socket = accept(&listener).await;
+		socket.send(welcome("session")).await.unwrap();
+		while socket.next().await.is_some() {}
+	});
+	url
+}
+
+#[tokio::test]
+async fn subscribes() {
+	let url = serve_session().await;
+	let requests = Requests::default();
+	let executor = Recorder {
+		requests: Arc::clone(&requests),
+		response: SUBSCRIPTION
+	};
+	let events = EventSub::connect_to(helix::Client::new(executor, "client", "token").unwrap(), url)
+		.await
+		.unwrap();
+
+	let subscription = events.subscribe(&Subscription::poll_begin("1337")).await.unwrap();
+	assert_eq!(subscription.id, "f1c2a387-161a-49f9-a165-0f21d7a4e1c4");
+	assert_eq!(subscription.status, "enabled");
+
+	let requests = requests.lock().unwrap();
+	let [request] = requests.as_slice() else {
+		panic!("expected one request, got {requests:?}");
+	};
+	assert_eq!(request.method(), http::Method::POST);
+	assert_eq!(request.uri(), "https://api.twitch.tv/helix/eventsub/subscriptions");
+	assert_eq!(
+		std::str::from_utf8(request.body()).unwrap(),
+		r#"{"type":"channel.poll.begin","version":"1","condition":{"broadcaster_user_id":"1337"},"transport":{"method":"websocket","session_id":"session"}}"#
+	);
+}
+
+#[tokio::test]
+async fn unsubscribes() {
+	let url = serve_session().await;
+	let requests = Requests::default();
+	let executor = Recorder {
+		requests: Arc::clone(&requests),
+		response: ""
+	};
+	let events = EventSub::connect_to(helix::Client::new(executor, "client", "token").unwrap(), url)
+		.await
+		.unwrap();
+
+	events.unsubscribe("f1c2a387-161a-49f9-a165-0f21d7a4e1c4").await.unwrap();
+
+	let requests = requests.lock().unwrap();
+	let [request] = requests.as_slice() else {
+		panic!("expected one request, got {requests:?}");
+	};
+	assert_eq!(request.method(), http::Method::DELETE);
+	assert_eq!(request.uri(), "https://api.twitch.tv/helix/eventsub/subscriptions?id=f1c2a387-161a-49f9-a165-0f21d7a4e1c4");
+	assert!(request.body().is_empty());
+}
+
+#[tokio::test]
+async fn receives_revocations() {
+	let (listener, url) = listen().await;
+	tokio::spawn(async move {
+		let mut socket = accept(&listener).await;
+		socket.send(welcome("session")).await.unwrap();
+		socket.send(revocation("1", "channel.poll.begin", "authorization_revoked")).await.unwrap();
+		while socket.next().await.is_some() {}
+	});
+
+	let mut events = EventSub::connect_to(client(), url).await.unwrap();
+	let event = tokio::time::timeout(Duration::from_secs(5), events.next()).await.unwrap();
+	let Some(Ok(Event::Revocation { subscription })) = event else {
+		panic!("expected a revocation, got {event:?}");
+	};
+	assert_eq!(subscription.kind, "channel.poll.begin");
+	assert_eq!(subscription.status, "authorization_revoked");
+	assert_eq!(subscription.condition["broadcaster_user_id"], "1337");
+}
@@ -0,0 +1,201 @@
+// Copyright 2026 pyke.io
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "twitch-helix")]
+
+use std::{
+	convert::Infallible,
+	sync::{Arc, Mutex},
+	time::Duration
+};
+
+use brainrot::twitch::{
+	AnnouncementColor, RequestExecutor, Response,
+	helix::{BanUser, ChatSettingsUpdate, Client}
+};
+use bytes::Bytes;
+use http::Method;
+
+const SETTINGS: &str = r#"{"data":[{"broadcaster_id":"1337","emote_mode":false,"follower_mode":true,"follower_mode_duration":10,"moderator_id":"42","non_moderator_chat_delay":false,"non_moderator_chat_delay_duration":null,"slow_mode":false,"slow_mode_wait_time":null,"subscriber_mode":false,"unique_chat_mode":false}]}"#;
+const BAN: &str = r#"{"data":[{"broadcaster_id":"1337","moderator_id":"42","user_id":"9001","created_at":"2026-01-01T00:00:00Z","end_time":null}]}"#;
+
+type Requests = Arc<Mutex<Vec<http::Request<Bytes>>>>;
+
+/// An executor that records each request and responds with the given body.
+#[derive(Debug, Clone)]
+struct Recorder {
+	requests: Requests,
+	response: &'static str
+}
+
+#[derive(Debug)]
+struct RecordedResponse(Option<Bytes>);
+
+impl Response for RecordedResponse {
+	type Error = Infallible;
+
+	fn status_code(&self) -> u16 {
+		200
+	}
+
+	async fn recv_chunk(&mut self) -> Result<Option<Bytes>, Self::Error> {
+		Ok(self.0.take())
+	}
+}
+
+impl RequestExecutor for Recorder {
+	type Response = RecordedResponse;
+	type Error = Infallible;
+
+	async fn make_request(&self, request: http::Request<Bytes>) -> Result<Self::Response, Self::Error> {
+		self.requests.lock().unwrap().push(request);
+		Ok(RecordedResponse(Some(Bytes::from_static(self.response.as_bytes()))))
+	}
+
+	async fn sleep(dur: Duration) {
+		tokio::time::sleep(dur).await;
+	}
+}
+
+fn mock(response: &'static str) -> (Client<Recorder>, Requests) {
+	let requests = Arc::default();
+	let executor = Recorder {
+		requests: Arc::clone(&requests),
+		response
+	};
+	(Client::new(executor, "client", "token").unwrap(), requests)
+}
+
+/// Asserts that the only request made was `method` to `path_and_query`, with the given body.
+fn assert_request(requests: &Requests, method: Method, path_and_query: &str, body: &str) {
+	let requests = requests.lock().unwrap();
+	let [request] = requests.as_slice() else {
+		panic!("expected one request, got {requests:?}");
+	};
+	assert_eq!(request.method(), method);
+	assert_eq!(request.uri().to_string(), format!("https://api.twitch.tv/helix{path_and_query}"));
+	assert_eq!(std::str::from_utf8(request.body()).unwrap(), body);
+	assert_eq!(request.headers()["client-id"], "client");
+	assert_eq!(request.headers()["authorization"], "Bearer token");
+}
+
+#[tokio::test]
+async fn chat_settings() {
+	let (client, requests) = mock(SETTINGS);
+	let settings = client.chat_settings(1337, Some(42)).await.unwrap();
+	assert_eq!(settings.follower_mode_duration, Some(10));
+	assert_request(&requests, Method::GET, "/chat/settings?broadcaster_id=1337&moderator_id=42", "");
+
+	let (client, requests) = mock(SETTINGS);
+	client.chat_settings(1337, None).await.unwrap();
+	assert_request(&requests, Method::GET, "/chat/settings?broadcaster_id=1337", "");
+}
+
+#[tokio::test]
+async fn update_chat_settings() {
+	let (client, requests) = mock(SETTINGS);
+	let update = ChatSettingsUpdate {
+		follower_mode: Some(true),
+		follower_mode_duration: Some(10),
+		..Default::default()
+	};
+	client.update_chat_settings(1337, 42, &update).await.unwrap();
+	assert_request(&requests, Method::PATCH, "/chat/settings?broadcaster_id=1337&moderator_id=42", r#"{"follower_mode":true,"follower_mode_duration":10}"#);
+}
+
+#[tokio::test]
+async fn send_shoutout() {
+	let (client, requests) = mock("");
+	client.send_shoutout(1337, 9001, 42).await.unwrap();
+	assert_request(&requests, Method::POST, "/chat/shoutouts?from_broadcaster_id=1337&to_broadcaster_id=9001&moderator_id=42", "");
+}
+
+#[tokio::test]
+async fn send_announcement() {
+	let (client, requests) = mock("");
+	client.send_announcement(1337, 42, "hi chat", &AnnouncementColor::Purple).await.unwrap();
+	assert_request(&requests, Method::POST, "/chat/announcements?broadcaster_id=1337&moderator_id=42", r#"{"message":"hi chat","color":"purple"}"#);
+}
+
+#[tokio::test]
+async fn send_chat_message() {
+	let (client, requests) = mock(r#"{"data":[{"message_id":"abc","is_sent":true,"drop_reason":null}]}"#);
+	let reply_to = "b34ccfc7-4977-403a-8a94-33c6bac34fb8".parse().unwrap();
+	let sent = client.send_chat_message(1337, 42, "hi chat", Some(reply_to)).await.unwrap();
+	assert!(sent.is_sent);
+	assert_request(
+		&requests,
+		Method::POST,
+		"/chat/messages",
+		r#"{"broadcaster_id":"1337","sender_id":"42","message":"hi chat","reply_parent_message_id":"b34ccfc7-4977-403a-8a94-33c6bac34fb8"}"#
+	);
+}
+
+#[tokio::test]
+async fn ban_user() {
+	let (client, requests) = mock(BAN);
+	let ban = client.ban_user(1337, 42, &BanUser::permanent(9001).with_reason("spam")).await.unwrap();
+	assert_eq!(ban.end_time, None);
+	assert_request(&requests, Method::POST, "/moderation/bans?broadcaster_id=1337&moderator_id=42", r#"{"data":{"user_id":"9001","reason":"spam"}}"#);
+}
+
+#[tokio::test]
+async fn timeout_durations_are_clamped() {
+	for (duration, secs) in [
+		(Duration::from_millis(1), 1),
+		(Duration::from_millis(1500), 2),
+		(Duration::from_secs(600), 600),
+		(Duration::from_secs(30 * 24 * 60 * 60), 1_209_600)
+	] {
+		let (client, requests) = mock(BAN);
+		client.ban_user(1337, 42, &BanUser::timeout(9001, duration)).await.unwrap();
+		assert_request(
+			&requests,
+			Method::POST,
+			"/moderation/bans?broadcaster_id=1337&moderator_id=42",
+			&format!(r#"{{"data":{{"user_id":"9001","duration":{secs}}}}}"#)
+		);
+	}
+}
+
+#[tokio::test]
+async fn unban_user() {
+	let (client, requests) = mock("");
+	client.unban_user(1337, 42, 9001).await.unwrap();
+	assert_request(&requests, Method::DELETE, "/moderation/bans?broadcaster_id=1337&moderator_id=42&user_id=9001", "");
+}
+
+#[tokio::test]
+async fn delete_message() {
+	let (client, requests) = mock("");
+	client
+		.delete_message(1337, 42, "b34ccfc7-4977-403a-8a94-33c6bac34fb8".parse().unwrap())
+		.await
+		.unwrap();
+	assert_request(&requests, Method::DELETE, "/moderation/chat?broadcaster_id=1337&moderator_id=42&message_id=b34ccfc7-4977-403a-8a94-33c6bac34fb8", "");
+}
+
+#[tokio::test]
+async fn clear_chat() {
+	let (client, requests) = mock("");
+	client.clear_chat(1337, 42).await.unwrap();
+	assert_request(&requests, Method::DELETE, "/moderation/chat?broadcaster_id=1337&moderator_id=42", "");
+}
+
+#[tokio::test]
+async fn send_whisper() {
+	let (client, requests) = mock("");
+	client.send_whisper(42, 9001, "hi").await.unwrap();
+	assert_request(&requests, Method::POST, "/whispers?from_user_id=42&to_user_id=9001", r#"{"message":"hi"}"#);
+}
@@ -0,0 +1,231 @@
+// Copyright 2026 pyke.io
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "twitch")]
+
+use std::{
+	io::{BufRead, BufReader, Write},
+	net::{TcpListener, TcpStream},
+	sync::mpsc,
+	thread,
+	time::{Duration, Instant}
+};
+
+use brainrot::twitch::{Anonymous, Chat, ChatEvent, DisconnectReason, ReconnectPolicy};
+use futures_util::StreamExt;
+
+const MESSAGE: &str = "@badge-info=;badges=;color=;display-name=miyukiwei;emotes=;id=b34ccfc7-4977-403a-8a94-33c6bac34fb8;mod=0;room-id=1;tmi-sent-ts=1;user-id=1;user-type= :miyukiwei!miyukiwei@miyukiwei.tmi.twitch.tv PRIVMSG #miyukiwei :hi\r\n";
+
+/// Welcomes the client on `socket`, and calls `on_join` for each `JOIN`. Every line received from the client is
+/// forwarded to `tx`. Returns once the connection is closed, or `on_join` returns `false`.
+fn serve_connection(socket: TcpStream, tx: &mpsc::Sender<String>, mut on_join: impl FnMut(&mut TcpStream) -> bool) {
+	let mut writer = socket.try_clone().unwrap();
+	for line in BufReader::new(socket).lines() {
+		let Ok(line) = line else {
+			break;
+		};
+		let command = line.split(' ').next().map(str::to_owned);
+		if tx.send(line).is_err() {
+			break;
+		}
+		match command.as_deref() {
+			Some("USER") if writer.write_all(b":tmi.twitch.tv 001 justinfan24340 :Welcome, GLHF!\r\n").is_err() => break,
+			Some("JOIN") if !on_join(&mut writer) => break,
+			_ => {}
+		}
+	}
+}
+
+/// Polls `chat` for up to 5 seconds, collecting events until `done` returns `true` or the stream ends.
+async fn collect(chat: &mut Chat, mut done: impl FnMut(&ChatEvent) -> bool) -> Vec<Result<ChatEvent, String>> {
+	let mut events = Vec::new();
+	tokio::time::timeout(Duration::from_secs(5), async {
+		while let Some(event) = chat.next().await {
+			let finished = event.as_ref().is_ok_and(&mut done);
+			events.push(event.map_err(|e| e.to_string()));
+			if finished {
+				break;
+			}
+		}
+	})
+	.await
+	.unwrap();
+	events
+}
+
+#[tokio::test]
+async fn rejoins_after_reconnect_request() {
+	let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+	let port = listener.local_addr().unwrap().port();
+	let (first_tx, first_rx) = mpsc::channel();
+	let (second_tx, second_rx) = mpsc::channel();
+	thread::spawn(move || {
+		let mut incoming = listener.incoming();
+		// Ask the client to reconnect once it has joined both channels.
+		let mut joins = 0;
+		serve_connection(incoming.next().unwrap().unwrap(), &first_tx, |socket| {
+			joins += 1;
+			joins < 2 || socket.write_all(b":tmi.twitch.tv RECONNECT\r\n").is_ok()
+		});
+		serve_connection(incoming.next().unwrap().unwrap(), &second_tx, |socket| socket.write_all(MESSAGE.as_bytes()).is_ok());
+	});
+
+	let mut chat = Chat::builder()
+		.server("127.0.0.1")
+		.port(port)
+		.tls(false)
+		.connect("miyukiwei", Anonymous)
+		.await
+		.unwrap()
+		.with_reconnect(ReconnectPolicy {
+			initial_delay: Duration::from_millis(10),
+			max_delay: Duration::from_millis(10),
+			max_attempts: Some(1)
+		});
+	chat.join("sinder");
+
+	let events = collect(&mut chat, |event| matches!(event, ChatEvent::Message { .. })).await;
+	assert!(
+		matches!(
+			events.as_slice(),
+			[
+				..,
+				Ok(ChatEvent::Disconnected {
+					reason: DisconnectReason::ServerRequested
+				}),
+				Ok(ChatEvent::Reconnected { attempts: 1 }),
+				Ok(ChatEvent::Message { .. })
+			]
+		),
+		"{events:?}"
+	);
+
+	let first: Vec<_> = first_rx.try_iter().collect();
+	let second: Vec<_> = second_rx.try_iter().collect();
+	for lines in [&first, &second] {
+		assert_eq!(lines[0], "CAP REQ :twitch.tv/commands twitch.tv/membership twitch.tv/tags");
+		assert!(lines.contains(&"JOIN #miyukiwei".to_owned()), "{lines:?}");
+		assert!(lines.contains(&"JOIN #sinder".to_owned()), "{lines:?}");
+	}
+}
+
+#[tokio::test]
+async fn gives_up_after_max_attempts() {
+	let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+	let port = listener.local_addr().unwrap().port();
+	thread::spawn(move || {
+		let (tx, _rx) = mpsc::channel();
+		// Drop the client once it has joined, and stop listening so that reconnection attempts are refused.
+		serve_connection(listener.accept().unwrap().0, &tx, |_| false);
+	});
+
+	let mut chat = Chat::builder()
+		.server("127.0.0.1")
+		.port(port)
+		.tls(false)
+		.connect("miyukiwei", Anonymous)
+		.await
+		.unwrap()
+		.with_reconnect(ReconnectPolicy {
+			initial_delay: Duration::from_millis(10),
+			max_delay: Duration::from_millis(10),
+			max_attempts: Some(2)
+		});
+
+	let events = collect(&mut chat, |_| false).await;
+	assert!(matches!(events.as_slice(), [.., Ok(ChatEvent::Disconnected { reason: DisconnectReason::Closed }), Err(_)]), "{events:?}");
+	assert!(!events.iter().any(|event| matches!(event, Ok(ChatEvent::Reconnected { .. }))));
+}
+
+/// A server that accepts connections and immediately closes them, before welcoming the client.
+fn serve_and_hang_up() -> u16 {
+	let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+	let port = listener.local_addr().unwrap().port();
+	thread::spawn(move || {
+		for socket in listener.incoming() {
+			drop(socket);
+		}
+	});
+	port
+}
+
+#[tokio::test]
+async fn backs_off_when_connections_close_before_welcome() {
+	let port = serve_and_hang_up();
+	let mut chat = Chat::builder()
+		.server("127.0.0.1")
+		.port(port)
+		.tls(false)
+		.connect("miyukiwei", Anonymous)
+		.await
+		.unwrap()
+		.with_reconnect(ReconnectPolicy {
+			initial_delay: Duration::from_millis(50),
+			max_delay: Duration::from_secs(1),
+			max_attempts: Some(3)
+		});
+
+	let start = Instant::now();
+	let mut reconnects = 0;
+	let result = tokio::time::timeout(Duration::from_secs(10), async {
+		while let Some(event) = chat.next().await {
+			if let Ok(ChatEvent::Reconnected { .. }) = event {
+				reconnects += 1;
+			}
+		}
+	})
+	.await;
+
+	assert!(result.is_ok(), "client kept reconnecting");
+	assert_eq!(reconnects, 3);
+	// Jittered delays of at least 25ms, 50ms & 100ms.
+	assert!(start.elapsed() >= Duration::from_millis(175), "reconnected without backoff in {:?}", start.elapsed());
+}
+
+#[tokio::test]
+async fn parts_only_joined_channels() {
+	let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+	let port = listener.local_addr().unwrap().port();
+	let (tx, rx) = mpsc::channel();
+	thread::spawn(move || {
+		serve_connection(listener.accept().unwrap().0, &tx, |socket| socket.write_all(MESSAGE.as_bytes()).is_ok());
+	});
+
+	let mut chat = Chat::builder()
+		.server("127.0.0.1")
+		.port(port)
+		.tls(false)
+		.connect("miyukiwei", Anonymous)
+		.await
+		.unwrap();
+	// Before registration, parting only cancels the pending `JOIN`.
+	chat.part("miyukiwei");
+	chat.part("nobody");
+	chat.join("sinder");
+	collect(&mut chat, |event| matches!(event, ChatEvent::Message { .. })).await;
+
+	chat.part("sinder");
+	chat.part("sinder");
+	assert_eq!(chat.channels().count(), 0);
+	// Drive the connection so the `PART` is flushed.
+	let _ = tokio::time::timeout(Duration::from_millis(100), chat.next()).await;
+
+	let mut commands = Vec::new();
+	while let Ok(line) = rx.recv_timeout(Duration::from_millis(500)) {
+		if line.starts_with("JOIN") || line.starts_with("PART") {
+			commands.push(line);
+		}
+	}
+	assert_eq!(commands, ["JOIN #sinder", "PART #sinder"]);
+}
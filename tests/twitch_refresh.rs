@@ -0,0 +1,178 @@
+// Copyright 2026 pyke.io
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "twitch-helix")]
+
+use std::{
+	convert::Infallible,
+	io::{BufRead, BufReader, Write},
+	net::TcpListener,
+	sync::{Arc, Mutex, mpsc},
+	thread,
+	time::Duration
+};
+
+use brainrot::twitch::{Chat, ChatEvent, ReconnectPolicy, RefreshingIdentity, RequestExecutor, Response, UserToken};
+use bytes::Bytes;
+use futures_util::StreamExt;
+
+const MESSAGE: &str = "@badge-info=;badges=;color=;display-name=miyukiwei;emotes=;id=b34ccfc7-4977-403a-8a94-33c6bac34fb8;mod=0;room-id=1;tmi-sent-ts=1;user-id=1;user-type= :miyukiwei!miyukiwei@miyukiwei.tmi.twitch.tv PRIVMSG #miyukiwei :hi\r\n";
+
+/// A token endpoint that issues `access1`, `access2`, ... and records the body of each request.
+#[derive(Debug, Clone, Default)]
+struct TokenEndpoint(Arc<Mutex<Vec<String>>>);
+
+#[derive(Debug)]
+struct TokenResponse(Option<Bytes>);
+
+impl Response for TokenResponse {
+	type Error = Infallible;
+
+	fn status_code(&self) -> u16 {
+		200
+	}
+
+	async fn recv_chunk(&mut self) -> Result<Option<Bytes>, Self::Error> {
+		Ok(self.0.take())
+	}
+}
+
+impl RequestExecutor for TokenEndpoint {
+	type Response = TokenResponse;
+	type Error = Infallible;
+
+	async fn make_request(&self, request: http::Request<Bytes>) -> Result<Self::Response, Self::Error> {
+		assert_eq!(request.method(), http::Method::POST);
+		assert_eq!(request.uri(), "https://id.twitch.tv/oauth2/token");
+		let mut requests = self.0.lock().unwrap();
+		requests.push(String::from_utf8(request.body().to_vec()).unwrap());
+		let n = requests.len();
+		Ok(TokenResponse(Some(Bytes::from(format!(r#"{{"access_token":"access{n}","refresh_token":"refresh{n}","expires_in":14400}}"#)))))
+	}
+
+	async fn sleep(dur: Duration) {
+		tokio::time::sleep(dur).await;
+	}
+}
+
+/// A server that only accepts the `access_token`, rejecting any other with a `Login authentication failed` notice,
+/// and sends a message once the client has joined a channel. The token each connection logs in with is forwarded to
+/// the returned channel.
+fn serve(access_token: &'static str) -> (u16, mpsc::Receiver<String>) {
+	let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+	let port = listener.local_addr().unwrap().port();
+	let (tx, rx) = mpsc::channel();
+	thread::spawn(move || {
+		for socket in listener.incoming() {
+			let socket = socket.unwrap();
+			let mut writer = socket.try_clone().unwrap();
+			let mut accepted = false;
+			for line in BufReader::new(socket).lines() {
+				let Ok(line) = line else {
+					break;
+				};
+				let response = if let Some(token) = line.strip_prefix("PASS oauth:") {
+					accepted = token == access_token;
+					if tx.send(token.to_owned()).is_err() {
+						return;
+					}
+					""
+				} else if line.starts_with("USER") {
+					if accepted {
+						":tmi.twitch.tv 001 yukifan4 :Welcome, GLHF!\r\n"
+					} else {
+						":tmi.twitch.tv NOTICE * :Login authentication failed\r\n"
+					}
+				} else if line.starts_with("JOIN") {
+					MESSAGE
+				} else {
+					""
+				};
+				if writer.write_all(response.as_bytes()).is_err() {
+					break;
+				}
+			}
+		}
+	});
+	(port, rx)
+}
+
+fn identity(executor: TokenEndpoint) -> RefreshingIdentity<TokenEndpoint> {
+	RefreshingIdentity::new(
+		executor,
+		"yukifan4",
+		"client id",
+		UserToken {
+			access_token: "access0".to_owned(),
+			refresh_token: "refresh+0/=".to_owned()
+		}
+	)
+}
+
+/// Polls `chat` until a message arrives, returning whether it reconnected first.
+async fn until_message(chat: &mut Chat) -> bool {
+	let mut reconnected = false;
+	tokio::time::timeout(Duration::from_secs(5), async {
+		loop {
+			match chat.next().await {
+				Some(Ok(ChatEvent::Message { .. })) => break,
+				Some(Ok(ChatEvent::Reconnected { .. })) => reconnected = true,
+				Some(Ok(_)) => {}
+				event => panic!("stream ended before the message arrived: {event:?}")
+			}
+		}
+	})
+	.await
+	.unwrap();
+	reconnected
+}
+
+#[tokio::test]
+async fn refreshes_before_expiry() {
+	let (port, rx) = serve("access1");
+	let executor = TokenEndpoint::default();
+	let mut chat = Chat::builder()
+		.server("127.0.0.1")
+		.port(port)
+		.tls(false)
+		.connect("miyukiwei", identity(executor.clone()).with_expiry(Duration::from_secs(60)))
+		.await
+		.unwrap();
+
+	assert!(!until_message(&mut chat).await);
+	assert_eq!(rx.try_iter().collect::<Vec<_>>(), ["access1"]);
+	assert_eq!(*executor.0.lock().unwrap(), ["grant_type=refresh_token&refresh_token=refresh%2B0%2F%3D&client_id=client%20id"]);
+}
+
+#[tokio::test]
+async fn refreshes_and_reconnects_when_rejected() {
+	let (port, rx) = serve("access1");
+	let executor = TokenEndpoint::default();
+	let mut chat = Chat::builder()
+		.server("127.0.0.1")
+		.port(port)
+		.tls(false)
+		.connect("miyukiwei", identity(executor.clone()))
+		.await
+		.unwrap()
+		.with_reconnect(ReconnectPolicy {
+			initial_delay: Duration::from_millis(10),
+			max_delay: Duration::from_millis(10),
+			max_attempts: Some(1)
+		});
+
+	assert!(until_message(&mut chat).await);
+	assert_eq!(rx.try_iter().collect::<Vec<_>>(), ["access0", "access1"]);
+	assert_eq!(executor.0.lock().unwrap().len(), 1);
+}
@@ -0,0 +1,136 @@
+// Copyright 2026 pyke.io
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "twitch-websocket")]
+
+use std::time::Duration;
+
+use brainrot::twitch::{Anonymous, Capabilities, Chat, ChatEvent, Error, Transport};
+use futures_util::{SinkExt, StreamExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::{WebSocketStream, tungstenite::Message};
+
+const WELCOME: &str = ":tmi.twitch.tv 001 justinfan24340 :Welcome, GLHF!\r\n";
+const MESSAGE: &str = "@badge-info=;badges=;color=;display-name=miyukiwei;emotes=;id=b34ccfc7-4977-403a-8a94-33c6bac34fb8;mod=0;room-id=1;tmi-sent-ts=1;user-id=1;user-type= :miyukiwei!miyukiwei@miyukiwei.tmi.twitch.tv PRIVMSG #miyukiwei :hi\r\n";
+
+async fn listen() -> (TcpListener, u16) {
+	let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+	let port = listener.local_addr().unwrap().port();
+	(listener, port)
+}
+
+async fn accept(listener: TcpListener) -> WebSocketStream<TcpStream> {
+	let (socket, _) = listener.accept().await.unwrap();
+	tokio_tungstenite::accept_async(socket).await.unwrap()
+}
+
+/// Receives the next text frame, or `None` once the client hangs up.
+async fn next_line(socket: &mut WebSocketStream<TcpStream>) -> Option<String> {
+	loop {
+		match socket.next().await? {
+			Ok(Message::Text(text)) => return Some(text.to_string()),
+			Ok(Message::Close(_)) | Err(_) => return None,
+			Ok(_) => {}
+		}
+	}
+}
+
+async fn connect(port: u16, ping_interval: Duration) -> Chat {
+	Chat::builder()
+		.transport(Transport::WebSocket)
+		.server("127.0.0.1")
+		.port(port)
+		.tls(false)
+		.capabilities(Capabilities { tags: true, ..Capabilities::none() })
+		.ping_interval(ping_interval)
+		.ping_timeout(Duration::from_secs(1))
+		.connect("miyukiwei", Anonymous)
+		.await
+		.unwrap()
+}
+
+#[tokio::test]
+async fn splits_frames_and_answers_pings() {
+	let (listener, port) = listen().await;
+	let server = tokio::spawn(async move {
+		let mut socket = accept(listener).await;
+		// One line per frame, without the trailing `\r\n`.
+		let mut registration = Vec::new();
+		for _ in 0..4 {
+			registration.push(next_line(&mut socket).await.unwrap());
+		}
+		assert_eq!(registration[..3], ["CAP REQ twitch.tv/tags", "CAP END", "NICK justinfan24340"]);
+		assert!(registration[3].starts_with("USER "));
+
+		// Several lines in a single frame, one of which can't be parsed.
+		socket
+			.send(Message::text(format!("{WELCOME}PING :tmi.twitch.tv\r\n:tmi.twitch.tv :malformed\r\n{MESSAGE}")))
+			.await
+			.unwrap();
+
+		let mut replies = vec![next_line(&mut socket).await.unwrap(), next_line(&mut socket).await.unwrap()];
+		replies.sort();
+		assert_eq!(replies, ["JOIN #miyukiwei", "PONG tmi.twitch.tv"]);
+		while next_line(&mut socket).await.is_some() {}
+	});
+
+	let mut chat = connect(port, Duration::from_secs(60)).await;
+	let message = tokio::time::timeout(Duration::from_secs(5), async {
+		loop {
+			match chat.next().await {
+				Some(Ok(ChatEvent::Message { contents, .. })) => break contents,
+				Some(Ok(_)) => {}
+				event => panic!("stream ended before the message arrived: {event:?}")
+			}
+		}
+	})
+	.await
+	.unwrap();
+	assert_eq!(message[0].to_string(), "hi");
+
+	// Keep polling so the `PONG` & `JOIN` are flushed.
+	let _ = tokio::time::timeout(Duration::from_millis(200), chat.next()).await;
+	drop(chat);
+	server.await.unwrap();
+}
+
+#[tokio::test]
+async fn times_out_without_pong() {
+	let (listener, port) = listen().await;
+	let server = tokio::spawn(async move {
+		let mut socket = accept(listener).await;
+		socket.send(Message::text(WELCOME)).await.unwrap();
+		let mut pinged = false;
+		while let Some(line) = next_line(&mut socket).await {
+			pinged |= line.starts_with("PING ");
+		}
+		pinged
+	});
+
+	let mut chat = connect(port, Duration::from_secs(1)).await;
+	let result = tokio::time::timeout(Duration::from_secs(5), async {
+		loop {
+			match chat.next().await {
+				Some(Ok(_)) => {}
+				event => break event
+			}
+		}
+	})
+	.await
+	.unwrap();
+	assert!(matches!(result, Some(Err(Error::PingTimeout))), "expected a ping timeout, got {result:?}");
+
+	drop(chat);
+	assert!(server.await.unwrap(), "client never sent a keepalive PING");
+}